@@ -0,0 +1,151 @@
+//! Surviving mutants which are accepted, typically because they are equivalent to the original
+//! code and so cannot be killed by any test.
+//!
+//! They are listed by ID (see [`crate::mutation::Mutation::id`]) in a TOML file, each with an
+//! optional justification:
+//!
+//! ```toml
+//! [[accepted]]
+//! id = "roman_numerals::to_roman:InstructionSwapping:ec784069"
+//! justification = "the numerals never reach the bound, so > and >= agree"
+//! ```
+
+use crate::Error;
+use serde::Deserialize;
+use std::collections::HashSet;
+use std::fs;
+use std::path::Path;
+
+/// A surviving mutant which is accepted.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize)]
+pub struct AcceptedMutant {
+    pub id: String,
+    /// Why the mutant cannot or need not be killed.
+    #[serde(default)]
+    pub justification: Option<String>,
+}
+
+impl AcceptedMutant {
+    /// Demangled path of the function of the mutant.
+    pub fn function(&self) -> &str {
+        function_path(&self.id)
+    }
+
+    /// Kind of the mutator which created the mutant, e.g. `InstructionSwapping`.
+    pub fn mutator(&self) -> &str {
+        self.id.rsplit(':').nth(1).unwrap_or_default()
+    }
+}
+
+/// Returns the part of a mutant ID before the name of the mutator and the hash.
+fn function_path(id: &str) -> &str {
+    id.rsplitn(3, ':').nth(2).unwrap_or_default()
+}
+
+/// The accepted surviving mutants of a project.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Deserialize)]
+pub struct AcceptedMutants {
+    #[serde(default)]
+    accepted: Vec<AcceptedMutant>,
+}
+
+impl AcceptedMutants {
+    /// Parses the contents of a file of accepted mutants.
+    pub fn parse(text: &str) -> Result<Self, Error> {
+        toml::from_str(text).map_err(|e| Error::AcceptedMutants(e.to_string()))
+    }
+
+    pub fn contains(&self, id: &str) -> bool {
+        self.accepted.iter().any(|mutant| mutant.id == id)
+    }
+
+    /// Reads the file of accepted mutants at the given path.
+    pub fn read(path: &Path) -> Result<Self, Error> {
+        let text = fs::read_to_string(path)
+            .map_err(|e| Error::AcceptedMutants(format!("{}: {}", path.display(), e)))?;
+        Self::parse(&text).map_err(|e| match e {
+            Error::AcceptedMutants(message) => {
+                Error::AcceptedMutants(format!("{}: {}", path.display(), message))
+            }
+            e => e,
+        })
+    }
+
+    /// Returns the accepted mutants which are no longer found in functions which were mutated,
+    /// given the IDs of all mutants of the run and the kinds of the mutators which were applied.
+    /// Accepted mutants of functions which were not mutated at all, e.g. because they were not
+    /// selected, or of mutators which were not applied are not reported.
+    pub fn missing<'a>(
+        &self,
+        ids: impl IntoIterator<Item = &'a str>,
+        mutators: &[&str],
+    ) -> Vec<AcceptedMutant> {
+        let ids: HashSet<&str> = ids.into_iter().collect();
+        let functions: HashSet<&str> = ids.iter().map(|id| function_path(id)).collect();
+        self.accepted
+            .iter()
+            .filter(|mutant| {
+                functions.contains(mutant.function())
+                    && mutators.contains(&mutant.mutator())
+                    && !ids.contains(mutant.id.as_str())
+            })
+            .cloned()
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::accepted::AcceptedMutants;
+    use crate::Error;
+
+    const ACCEPTED: &str = r#"
+[[accepted]]
+id = "roman_numerals::to_roman:InstructionSwapping:ec784069"
+justification = "equivalent"
+
+[[accepted]]
+id = "<roman_numerals::Numeral as core::fmt::Display>::fmt:IfConditionToTrue:0123abcd-1"
+"#;
+
+    #[test]
+    fn parses_accepted_mutants_with_optional_justification() {
+        let accepted = AcceptedMutants::parse(ACCEPTED).unwrap();
+
+        assert_eq!(accepted.accepted.len(), 2);
+        assert_eq!(
+            accepted.accepted[0].justification.as_deref(),
+            Some("equivalent")
+        );
+        assert_eq!(accepted.accepted[1].justification, None);
+        assert_eq!(
+            accepted.accepted[1].function(),
+            "<roman_numerals::Numeral as core::fmt::Display>::fmt"
+        );
+        assert!(accepted.contains("roman_numerals::to_roman:InstructionSwapping:ec784069"));
+    }
+
+    #[test]
+    fn finds_accepted_mutants_missing_from_mutated_functions() {
+        let accepted = AcceptedMutants::parse(ACCEPTED).unwrap();
+
+        let missing = accepted.missing(
+            ["roman_numerals::to_roman:InstructionSwapping:0000ffff"],
+            &["InstructionSwapping"],
+        );
+
+        assert_eq!(missing.len(), 1);
+        assert_eq!(missing[0].mutator(), "InstructionSwapping");
+        assert_eq!(
+            missing[0].id,
+            "roman_numerals::to_roman:InstructionSwapping:ec784069"
+        );
+    }
+
+    #[test]
+    fn fails_on_malformed_file() {
+        let result = AcceptedMutants::parse("[[accepted]]\njustification = \"no id\"\n");
+
+        assert!(matches!(result, Err(Error::AcceptedMutants(_))));
+    }
+}
@@ -0,0 +1,247 @@
+use cargo_metadata::{Message, Metadata, MetadataCommand, PackageId};
+use clap::{Args, Parser, Subcommand};
+use mutation_test_engine::{
+    cli::CommonOptions, mutation, report, threshold, AcceptedMutants, ChangedLines, Config,
+    MutationTestRun,
+};
+use std::fs;
+use std::io::BufReader;
+use std::path::PathBuf;
+use std::process::{self, Command, Stdio};
+
+const EXIT_ERROR: i32 = 1;
+const EXIT_BELOW_THRESHOLD: i32 = 3;
+
+#[derive(Parser, Debug)]
+#[command(name = "cargo", bin_name = "cargo")]
+enum Cargo {
+    Mutate(MutateOptions),
+}
+
+/// Builds the tests of a crate for a WebAssembly target and reports the mutants of its code which
+/// the tests fail to detect.
+///
+/// The functions of the crate are mutated unless include patterns are given, and those of its
+/// `tests` module never are.
+#[derive(Args, Debug)]
+#[command(version, args_conflicts_with_subcommands = true)]
+struct MutateOptions {
+    #[command(subcommand)]
+    command: Option<MutateCommand>,
+
+    /// Path to the Cargo.toml of the package or workspace to test
+    #[arg(long, value_name = "PATH")]
+    manifest_path: Option<PathBuf>,
+
+    /// Package to test (repeatable)
+    #[arg(short, long, value_name = "SPEC")]
+    package: Vec<String>,
+
+    /// Test all members of the workspace
+    #[arg(long)]
+    workspace: bool,
+
+    /// WebAssembly target for which to build the tests
+    #[arg(long, value_name = "TRIPLE", default_value = "wasm32-unknown-unknown")]
+    target: String,
+
+    #[command(flatten)]
+    common: CommonOptions,
+}
+
+#[derive(Subcommand, Debug)]
+enum MutateCommand {
+    /// List the mutators which can be enabled or disabled, with their IDs and kinds
+    ListMutators,
+}
+
+/// A test binary produced by the build, along with the crate whose code it tests.
+struct TestArtifact {
+    crate_name: String,
+    module: PathBuf,
+}
+
+fn main() {
+    let Cargo::Mutate(mut options) = Cargo::parse();
+    if let Some(MutateCommand::ListMutators) = options.command {
+        print!("{}", mutation::list_mutators());
+        return;
+    }
+    let config = match std::env::current_dir()
+        .map_err(|e| format!("could not find the working directory: {}", e))
+        .and_then(|directory| {
+            Config::load(options.common.config.as_deref(), &directory).map_err(|e| e.to_string())
+        }) {
+        Ok(config) => config,
+        Err(message) => {
+            eprintln!("error: {}", message);
+            process::exit(EXIT_ERROR);
+        }
+    };
+    if let Err(e) = options.common.merge(&config) {
+        eprintln!("error: {}", e);
+        process::exit(EXIT_ERROR);
+    }
+    if config.report.format.is_some() || config.report.output.is_some() {
+        eprintln!(
+            "warning: cargo mutate ignores the `format` and `output` report settings and writes a \
+             text report to standard output"
+        );
+    }
+    match run(&options) {
+        Ok(true) => {}
+        Ok(false) => process::exit(EXIT_BELOW_THRESHOLD),
+        Err(message) => {
+            eprintln!("error: {}", message);
+            process::exit(EXIT_ERROR);
+        }
+    }
+}
+
+/// Runs the mutants of each test binary and reports them, returning whether all reached the
+/// thresholds.
+fn run(options: &MutateOptions) -> Result<bool, String> {
+    let metadata = read_metadata(options)?;
+    let artifacts = build_tests(options, &metadata)?;
+    if artifacts.is_empty() {
+        return Err(String::from("the build produced no test binaries"));
+    }
+    let changed_lines = read_changed_lines(options, &metadata)?;
+    let mut passed = true;
+    let common = &options.common;
+    let accepted_mutants = match &common.accepted {
+        Some(path) => AcceptedMutants::read(path).map_err(|e| e.to_string())?,
+        None => AcceptedMutants::default(),
+    };
+    for artifact in artifacts {
+        let contents = fs::read(&artifact.module)
+            .map_err(|e| format!("could not read {}: {}", artifact.module.display(), e))?;
+        let mut run = common
+            .configure(MutationTestRun::new(&contents))
+            .exclude_module(format!("{}::tests", artifact.crate_name))
+            .accepted_mutants(accepted_mutants.clone());
+        if let Some(changed_lines) = &changed_lines {
+            run = run.changed_lines(changed_lines.clone());
+        }
+        run = if common.include.is_empty() {
+            run.include_module(&artifact.crate_name)
+        } else {
+            run.include_modules(&common.include)
+        };
+        let results = run
+            .run()
+            .map_err(|e| format!("{}: {}", artifact.module.display(), e))?;
+        for mutant in &results.missing_accepted {
+            eprintln!("warning: accepted mutant {} no longer exists", mutant.id);
+        }
+        let surviving_mutants: Vec<_> = results.surviving_mutants().collect();
+        println!(
+            "{} ({}): function names read from {}\nSurviving mutants: {:?}\n{}",
+            artifact.crate_name,
+            artifact.module.display(),
+            results.naming_source,
+            surviving_mutants,
+            results.totals()
+        );
+        if common.show_diffs {
+            let diffs = report::surviving_mutant_diffs(&results, &contents)
+                .map_err(|e| format!("{}: {}", artifact.module.display(), e))?;
+            for (mutation, diff) in diffs {
+                println!("{:?}\n{}", mutation, diff);
+            }
+        }
+        for threshold in threshold::failed_thresholds(&results, &common.min_score) {
+            eprintln!("error: {}: {}", artifact.crate_name, threshold);
+            passed = false;
+        }
+    }
+    Ok(passed)
+}
+
+/// Reads the lines changed by the given diff, or since the given revision of the repository
+/// containing the workspace.
+fn read_changed_lines(
+    options: &MutateOptions,
+    metadata: &Metadata,
+) -> Result<Option<ChangedLines>, String> {
+    if let Some(path) = &options.common.diff {
+        ChangedLines::read(path)
+            .map(Some)
+            .map_err(|e| e.to_string())
+    } else if let Some(base) = &options.common.git_base {
+        ChangedLines::from_git(metadata.workspace_root.as_std_path(), base)
+            .map(Some)
+            .map_err(|e| e.to_string())
+    } else {
+        Ok(None)
+    }
+}
+
+fn read_metadata(options: &MutateOptions) -> Result<Metadata, String> {
+    let mut command = MetadataCommand::new();
+    if let Some(manifest_path) = &options.manifest_path {
+        command.manifest_path(manifest_path);
+    }
+    command
+        .no_deps()
+        .exec()
+        .map_err(|e| format!("could not read the package metadata: {}", e))
+}
+
+fn build_tests(options: &MutateOptions, metadata: &Metadata) -> Result<Vec<TestArtifact>, String> {
+    let mut command = Command::new(std::env::var("CARGO").unwrap_or_else(|_| "cargo".into()));
+    command.args([
+        "test",
+        "--no-run",
+        "--message-format=json-render-diagnostics",
+        "--target",
+        &options.target,
+    ]);
+    if let Some(manifest_path) = &options.manifest_path {
+        command.arg("--manifest-path").arg(manifest_path);
+    }
+    if options.workspace {
+        command.arg("--workspace");
+    }
+    for package in &options.package {
+        command.arg("--package").arg(package);
+    }
+    let mut child = command
+        .stdout(Stdio::piped())
+        .spawn()
+        .map_err(|e| format!("could not run cargo: {}", e))?;
+
+    let mut artifacts = Vec::new();
+    let stdout = BufReader::new(child.stdout.take().unwrap());
+    for message in Message::parse_stream(stdout) {
+        let message = message.map_err(|e| format!("could not read cargo output: {}", e))?;
+        if let Message::CompilerArtifact(artifact) = message {
+            if let (true, Some(module)) = (artifact.profile.test, artifact.executable) {
+                artifacts.push(TestArtifact {
+                    crate_name: crate_name(metadata, &artifact.package_id),
+                    module: module.into(),
+                });
+            }
+        }
+    }
+
+    let status = child
+        .wait()
+        .map_err(|e| format!("could not run cargo: {}", e))?;
+    if !status.success() {
+        return Err(String::from("building the tests failed"));
+    }
+    Ok(artifacts)
+}
+
+/// Returns the name under which the code of the given package appears in symbol names, i.e. the
+/// name of its library target, or the package name if it has none.
+fn crate_name(metadata: &Metadata, package_id: &PackageId) -> String {
+    let package = &metadata[package_id];
+    package
+        .targets
+        .iter()
+        .find(|target| target.kind.iter().any(|kind| kind == "lib"))
+        .map_or(&package.name, |target| &target.name)
+        .replace('-', "_")
+}
@@ -1,65 +1,177 @@
-use parity_wasm::elements::Module;
-use rustc_demangle::demangle;
+mod naming;
+mod pattern;
+mod symbol;
 
+use crate::debug_info::InlinedCode;
+use crate::Error;
+use parity_wasm::elements::{ImportCountType, Module};
+
+pub use naming::{FunctionNames, NamingSource};
+pub use pattern::Pattern;
+pub use symbol::SymbolPath;
+
+/// Exclude patterns applied unless disabled: drop glue, the generic code of the standard library
+/// instantiated for the types of the crate under test, including the `core::fmt` machinery, and
+/// the compiler's builtins. Such code is not written by the user and its mutants only add noise.
+pub const DEFAULT_EXCLUDE_PATTERNS: [&str; 7] = [
+    "core::ptr::drop_in_place",
+    "core::ptr::real_drop_in_place",
+    "crate:core",
+    "crate:alloc",
+    "crate:std",
+    "crate:compiler_builtins",
+    "core::fmt",
+];
+
+/// A function defined by the module which may be mutated.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CandidateFunction {
+    /// Index of the function in the function index space of the module, which counts imported
+    /// functions first. This is the index used by the name section and by `call` instructions.
+    pub function_index: u32,
+    /// Index of the body of the function in the code section, which only holds the functions
+    /// defined by the module.
+    pub body_index: usize,
+    /// Demangled name of the function, without its hash.
+    pub name: String,
+    /// Structured path of the function, parsed from its name.
+    pub path: SymbolPath,
+}
+
+/// Include and exclude patterns which together select the functions to mutate.
+#[derive(Clone, Debug)]
+pub struct FunctionFilter {
+    include_patterns: Vec<Pattern>,
+    exclude_patterns: Vec<Pattern>,
+}
+
+impl FunctionFilter {
+    pub fn new(include_modules: &[&str], exclude_modules: &[&str]) -> Result<Self, Error> {
+        Ok(FunctionFilter {
+            include_patterns: compile_patterns(include_modules)?,
+            exclude_patterns: compile_patterns(exclude_modules)?,
+        })
+    }
+
+    /// Whether the function matches one of the include patterns and none of the exclude patterns.
+    pub fn selects(&self, path: &SymbolPath) -> bool {
+        self.include_patterns
+            .iter()
+            .any(|pattern| pattern.matches(path))
+            && !self
+                .exclude_patterns
+                .iter()
+                .any(|pattern| pattern.matches(path))
+    }
+}
+
+/// Returns the functions defined by the module whose name, as given by the function names read
+/// from it, matches one of the include patterns and none of the exclude patterns (see
+/// [`Pattern`]), in order of function index.
 pub fn identify_candidate_functions(
     module: &Module,
+    function_names: &FunctionNames,
     include_modules: &[&str],
     exclude_modules: &[&str],
-) -> Vec<usize> {
-    let mut result = Vec::<usize>::new();
-    for function in module.names_section().unwrap().functions() {
-        for name in function.names() {
-            let demangled_name = demangle(name.1);
-            for include_module in include_modules {
-                if demangled_name.to_string().starts_with(include_module) {
-                    let mut included = true;
-                    for exclude_module in exclude_modules {
-                        if demangled_name.to_string().starts_with(exclude_module) {
-                            included = false;
-                        }
-                    }
-                    if included {
-                        result.push(name.0 as usize);
-                    }
-                }
+) -> Result<Vec<CandidateFunction>, Error> {
+    let filter = FunctionFilter::new(include_modules, exclude_modules)?;
+    Ok(select_functions(module, function_names, &filter))
+}
+
+/// Returns the functions defined by the module which the filter selects, in order of function
+/// index.
+pub(crate) fn select_functions(
+    module: &Module,
+    function_names: &FunctionNames,
+    filter: &FunctionFilter,
+) -> Vec<CandidateFunction> {
+    defined_functions(module, function_names)
+        .filter(|candidate| filter.selects(&candidate.path))
+        .collect()
+}
+
+/// Returns the functions defined by the module which the filter does not select, but into which
+/// the compiler inlined code of a function it selects, in order of function index.
+pub(crate) fn identify_functions_with_inlined_candidates(
+    module: &Module,
+    function_names: &FunctionNames,
+    filter: &FunctionFilter,
+    inlined_code: &InlinedCode,
+) -> Vec<CandidateFunction> {
+    defined_functions(module, function_names)
+        .filter(|candidate| {
+            !filter.selects(&candidate.path)
+                && inlined_code
+                    .functions_in(candidate.body_index)
+                    .iter()
+                    .any(|inlined| filter.selects(&SymbolPath::from_symbol(&inlined.name)))
+        })
+        .collect()
+}
+
+fn defined_functions<'a>(
+    module: &Module,
+    function_names: &'a FunctionNames,
+) -> impl Iterator<Item = CandidateFunction> + 'a {
+    let imported_functions = module.import_count(ImportCountType::Function) as u32;
+    function_names
+        .names
+        .range(imported_functions..)
+        .map(move |(&function_index, name)| {
+            let path = SymbolPath::from_symbol(name);
+            CandidateFunction {
+                function_index,
+                body_index: (function_index - imported_functions) as usize,
+                name: path.to_string(),
+                path,
             }
-        }
-    }
-    result
+        })
+}
+
+fn compile_patterns(sources: &[&str]) -> Result<Vec<Pattern>, Error> {
+    sources.iter().map(|source| Pattern::new(source)).collect()
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::candidates::identify_candidate_functions;
+    use crate::candidates::{
+        identify_candidate_functions, FunctionNames, Pattern, SymbolPath, DEFAULT_EXCLUDE_PATTERNS,
+    };
+    use crate::test_support::module_with_imported_functions;
+    use crate::Error;
     use parity_wasm::deserialize_buffer;
-    use parity_wasm::elements::Module;
+    use parity_wasm::elements::{Instruction, Module};
     use rustc_demangle::demangle;
 
-    #[test]
-    fn returns_no_functions_when_none_included() {
-        let module = deserialize_buffer::<Module>(include_bytes!("res/cases/test-example.wasm"))
+    fn read_module(contents: &[u8]) -> (Module, FunctionNames) {
+        let module = deserialize_buffer::<Module>(contents)
             .unwrap()
             .parse_names()
             .unwrap();
+        let names = FunctionNames::read(&module, contents).unwrap();
+        (module, names)
+    }
+
+    #[test]
+    fn returns_no_functions_when_none_included() {
+        let (module, names) = read_module(include_bytes!("res/cases/test-example.wasm"));
 
-        let result = identify_candidate_functions(&module, &vec![], &vec![]);
+        let result = identify_candidate_functions(&module, &names, &[], &[]).unwrap();
 
         assert_eq!(result.len(), 0);
     }
 
     #[test]
     fn returns_functions_in_include_modules() {
-        let module = deserialize_buffer::<Module>(include_bytes!("res/cases/test-example.wasm"))
-            .unwrap()
-            .parse_names()
-            .unwrap();
+        let (module, names) = read_module(include_bytes!("res/cases/test-example.wasm"));
 
-        let result = identify_candidate_functions(&module, &vec!["roman_numerals"], &vec![]);
+        let result =
+            identify_candidate_functions(&module, &names, &["roman_numerals"], &[]).unwrap();
 
         let name_map = module.names_section().unwrap().functions().unwrap().names();
         let names: Vec<String> = result
             .into_iter()
-            .map(|index| demangle(name_map.get(index as u32).unwrap()).to_string())
+            .map(|candidate| demangle(name_map.get(candidate.function_index).unwrap()).to_string())
             .collect();
         let to_roman_name: Vec<String> = names
             .into_iter()
@@ -70,21 +182,20 @@ mod tests {
 
     #[test]
     fn does_not_return_functions_in_exclude_modules() {
-        let module = deserialize_buffer::<Module>(include_bytes!("res/cases/test-example.wasm"))
-            .unwrap()
-            .parse_names()
-            .unwrap();
+        let (module, names) = read_module(include_bytes!("res/cases/test-example.wasm"));
 
         let result = identify_candidate_functions(
             &module,
-            &vec!["roman_numerals"],
-            &vec!["roman_numerals::tests"],
-        );
+            &names,
+            &["roman_numerals"],
+            &["roman_numerals::tests"],
+        )
+        .unwrap();
 
         let name_map = module.names_section().unwrap().functions().unwrap().names();
         let names: Vec<String> = result
             .into_iter()
-            .map(|index| demangle(name_map.get(index as u32).unwrap()).to_string())
+            .map(|candidate| demangle(name_map.get(candidate.function_index).unwrap()).to_string())
             .collect();
         let to_roman_name: Vec<String> = names
             .into_iter()
@@ -92,4 +203,88 @@ mod tests {
             .collect();
         assert_eq!(to_roman_name.len(), 0);
     }
+
+    #[test]
+    fn translates_function_indices_into_body_indices_when_module_imports_functions() {
+        let (module, names) = read_module(&module_with_imported_functions(
+            &["env::print", "env::exit"],
+            &[
+                ("imports::first", vec![Instruction::End]),
+                ("imports::second", vec![Instruction::End]),
+            ],
+        ));
+
+        let result =
+            identify_candidate_functions(&module, &names, &["imports", "env"], &[]).unwrap();
+
+        let identities: Vec<(u32, usize, &str)> = result
+            .iter()
+            .map(|c| (c.function_index, c.body_index, c.name.as_str()))
+            .collect();
+        assert_eq!(
+            identities,
+            vec![(3, 1, "imports::first"), (4, 2, "imports::second")]
+        );
+    }
+
+    #[test]
+    fn returns_function_matched_by_several_include_patterns_once() {
+        let (module, names) = read_module(include_bytes!("res/cases/test-example.wasm"));
+
+        let result = identify_candidate_functions(
+            &module,
+            &names,
+            &["roman_numerals", "roman_numerals::to_roman", "re:to_roman"],
+            &[],
+        )
+        .unwrap();
+
+        let to_roman: Vec<_> = result
+            .iter()
+            .filter(|candidate| candidate.name.starts_with("roman_numerals::to_roman"))
+            .collect();
+        assert_eq!(to_roman.len(), 1);
+    }
+
+    #[test]
+    fn matches_include_patterns_against_whole_path_segments() {
+        let (module, names) = read_module(include_bytes!("res/cases/test-example.wasm"));
+
+        let result = identify_candidate_functions(&module, &names, &["roman"], &[]).unwrap();
+
+        assert_eq!(result.len(), 0);
+    }
+
+    #[test]
+    fn fails_on_invalid_pattern() {
+        let (module, names) = read_module(include_bytes!("res/cases/test-example.wasm"));
+
+        let result = identify_candidate_functions(&module, &names, &["re:["], &[]);
+
+        assert!(matches!(result, Err(Error::InvalidPattern(_, _))));
+    }
+
+    #[test]
+    fn default_exclude_patterns_exclude_library_code_but_not_user_impls() {
+        let patterns: Vec<Pattern> = DEFAULT_EXCLUDE_PATTERNS
+            .iter()
+            .map(|source| Pattern::new(source).unwrap())
+            .collect();
+        let excluded = |name: &str| {
+            let path = SymbolPath::parse(name);
+            patterns.iter().any(|pattern| pattern.matches(&path))
+        };
+
+        assert!(excluded(
+            "core::ptr::drop_in_place<roman_numerals::Numeral>"
+        ));
+        assert!(excluded("<alloc::vec::Vec<roman_numerals::Numeral>>::push"));
+        assert!(excluded("<&T as core::fmt::Display>::fmt"));
+        assert!(excluded("core::fmt::Formatter::pad"));
+        assert!(excluded("compiler_builtins::mem::memcpy"));
+        assert!(!excluded(
+            "<roman_numerals::Numeral as core::fmt::Display>::fmt"
+        ));
+        assert!(!excluded("roman_numerals::to_roman"));
+    }
 }
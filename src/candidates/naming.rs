@@ -0,0 +1,314 @@
+use crate::debug_info::subprograms;
+use crate::Error;
+use parity_wasm::elements::{ImportCountType, Internal, Module};
+use std::collections::BTreeMap;
+use std::fmt::{self, Display, Formatter};
+
+const LINKING_SECTION: &str = "linking";
+const SYMBOL_TABLE_SUBSECTION: u8 = 8;
+const FUNCTION_SYMBOL: u8 = 0;
+const DATA_SYMBOL: u8 = 1;
+const SECTION_SYMBOL: u8 = 3;
+const UNDEFINED_SYMBOL_FLAG: u32 = 0x10;
+const EXPLICIT_NAME_SYMBOL_FLAG: u32 = 0x40;
+
+type NameReader = fn(&Module, &[u8]) -> BTreeMap<u32, String>;
+
+/// Where the names of the functions of a module were found.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum NamingSource {
+    /// The `name` custom section, written by the linker unless the module is stripped.
+    #[default]
+    NameSection,
+    /// The symbol table in the `linking` custom section of a relocatable module.
+    Linking,
+    /// The subprograms of the DWARF `.debug_info` custom section.
+    Dwarf,
+    /// The names under which functions are exported.
+    Exports,
+}
+
+impl Display for NamingSource {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            NamingSource::NameSection => "the name section",
+            NamingSource::Linking => "the symbol table of the linking section",
+            NamingSource::Dwarf => "the DWARF debugging information",
+            NamingSource::Exports => "the exports",
+        })
+    }
+}
+
+/// Names of the functions of a module, keyed by index in the function index space.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FunctionNames {
+    pub source: NamingSource,
+    pub names: BTreeMap<u32, String>,
+}
+
+impl FunctionNames {
+    /// Reads the names of the functions from the first of the name section, the `linking`
+    /// section, the DWARF debugging information and the exports which names any function. The
+    /// `producers` section only records the tools which built the module, so names no function.
+    ///
+    /// The module must have been parsed from the given contents, which are needed to locate the
+    /// functions described by the DWARF debugging information.
+    pub fn read(module: &Module, module_contents: &[u8]) -> Result<Self, Error> {
+        let sources: [(NamingSource, NameReader); 4] = [
+            (NamingSource::NameSection, |module, _| {
+                names_from_name_section(module)
+            }),
+            (NamingSource::Linking, |module, _| {
+                names_from_linking_section(module)
+            }),
+            (NamingSource::Dwarf, names_from_dwarf),
+            (NamingSource::Exports, |module, _| {
+                names_from_exports(module)
+            }),
+        ];
+        sources
+            .iter()
+            .map(|(source, read)| FunctionNames {
+                source: *source,
+                names: read(module, module_contents),
+            })
+            .find(|names| !names.names.is_empty())
+            .ok_or(Error::NoFunctionNames)
+    }
+}
+
+fn names_from_name_section(module: &Module) -> BTreeMap<u32, String> {
+    module
+        .names_section()
+        .and_then(|section| section.functions())
+        .map(|functions| {
+            functions
+                .names()
+                .iter()
+                .map(|(index, name)| (index, name.clone()))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+fn names_from_exports(module: &Module) -> BTreeMap<u32, String> {
+    module
+        .export_section()
+        .map(|section| {
+            section
+                .entries()
+                .iter()
+                .filter_map(|export| match export.internal() {
+                    Internal::Function(index) => Some((*index, String::from(export.field()))),
+                    _ => None,
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Reads the names of the defined function symbols in the symbol table of the `linking` section.
+/// A malformed section yields the names read before the error.
+fn names_from_linking_section(module: &Module) -> BTreeMap<u32, String> {
+    let mut names = BTreeMap::new();
+    let payload = match module
+        .custom_sections()
+        .find(|section| section.name() == LINKING_SECTION)
+    {
+        Some(section) => section.payload(),
+        None => return names,
+    };
+    let mut reader = ByteReader::new(payload);
+    let _version = reader.read_u32();
+    while let (Some(kind), Some(length)) = (reader.read_u8(), reader.read_u32()) {
+        let subsection = match reader.read_bytes(length as usize) {
+            Some(subsection) => subsection,
+            None => break,
+        };
+        if kind == SYMBOL_TABLE_SUBSECTION {
+            read_function_symbols(&mut ByteReader::new(subsection), &mut names);
+        }
+    }
+    names
+}
+
+fn read_function_symbols(reader: &mut ByteReader, names: &mut BTreeMap<u32, String>) -> Option<()> {
+    let count = reader.read_u32()?;
+    for _ in 0..count {
+        let kind = reader.read_u8()?;
+        let flags = reader.read_u32()?;
+        let defined = flags & UNDEFINED_SYMBOL_FLAG == 0;
+        if kind == DATA_SYMBOL {
+            reader.read_name()?;
+            if defined {
+                // Segment index, offset and size.
+                for _ in 0..3 {
+                    reader.read_u32()?;
+                }
+            }
+            continue;
+        }
+        let index = reader.read_u32()?;
+        let has_name = defined || flags & EXPLICIT_NAME_SYMBOL_FLAG != 0;
+        if has_name && kind != SECTION_SYMBOL {
+            let name = reader.read_name()?;
+            if kind == FUNCTION_SYMBOL && defined {
+                names.insert(index, name);
+            }
+        }
+    }
+    Some(())
+}
+
+/// Reads the names of the subprograms in the DWARF debugging information.
+fn names_from_dwarf(module: &Module, module_contents: &[u8]) -> BTreeMap<u32, String> {
+    let first_defined = module.import_count(ImportCountType::Function) as u32;
+    let mut names = BTreeMap::new();
+    for subprogram in subprograms(module, module_contents) {
+        if let Some(name) = subprogram.name {
+            names
+                .entry(first_defined + subprogram.body_index as u32)
+                .or_insert(name);
+        }
+    }
+    names
+}
+
+/// Reads the LEB128 encoded integers and names of a custom section.
+struct ByteReader<'a> {
+    bytes: &'a [u8],
+    position: usize,
+}
+
+impl<'a> ByteReader<'a> {
+    fn new(bytes: &'a [u8]) -> Self {
+        ByteReader { bytes, position: 0 }
+    }
+
+    fn read_u8(&mut self) -> Option<u8> {
+        let byte = *self.bytes.get(self.position)?;
+        self.position += 1;
+        Some(byte)
+    }
+
+    fn read_u32(&mut self) -> Option<u32> {
+        let mut result: u32 = 0;
+        for shift in (0..35).step_by(7) {
+            let byte = self.read_u8()?;
+            result |= u32::from(byte & 0x7f).checked_shl(shift)?;
+            if byte & 0x80 == 0 {
+                return Some(result);
+            }
+        }
+        None
+    }
+
+    fn read_bytes(&mut self, length: usize) -> Option<&'a [u8]> {
+        let bytes = self
+            .bytes
+            .get(self.position..self.position.checked_add(length)?)?;
+        self.position += length;
+        Some(bytes)
+    }
+
+    fn read_name(&mut self) -> Option<String> {
+        let length = self.read_u32()?;
+        let bytes = self.read_bytes(length as usize)?;
+        Some(String::from_utf8_lossy(bytes).into_owned())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::candidates::naming::{FunctionNames, NamingSource};
+    use crate::runner::tests::test_module;
+    use crate::test_support::without_custom_section;
+    use crate::Error;
+    use parity_wasm::builder::ModuleBuilder;
+    use parity_wasm::deserialize_buffer;
+    use parity_wasm::elements::{CustomSection, Instruction, Module, Section};
+
+    fn read_names(contents: &[u8]) -> Result<FunctionNames, Error> {
+        let module = deserialize_buffer::<Module>(contents)
+            .unwrap()
+            .parse_names()
+            .unwrap();
+        FunctionNames::read(&module, contents)
+    }
+
+    #[test]
+    fn reads_names_from_name_section() {
+        let names = read_names(include_bytes!("res/cases/test-example.wasm")).unwrap();
+
+        assert_eq!(names.source, NamingSource::NameSection);
+    }
+
+    #[test]
+    fn falls_back_to_dwarf_when_name_section_is_stripped() {
+        let contents = include_bytes!("res/cases/test-example.wasm");
+        let name_section_names = read_names(contents).unwrap().names;
+
+        let names = read_names(&without_custom_section(contents, "name")).unwrap();
+
+        assert_eq!(names.source, NamingSource::Dwarf);
+        let to_roman = names
+            .names
+            .iter()
+            .find(|(_, name)| name.contains("roman_numerals8to_roman"))
+            .unwrap();
+        assert_eq!(name_section_names.get(to_roman.0), Some(to_roman.1));
+    }
+
+    #[test]
+    fn falls_back_to_linking_section() {
+        let mut module =
+            deserialize_buffer::<Module>(&test_module(vec![Instruction::End])).unwrap();
+        let symbol_table = [
+            1, // One symbol
+            0, // Function
+            0, // Defined, binding global
+            0, // Function index
+            4, b't', b'e', b's', b't',
+        ];
+        let mut payload = vec![2, 8, symbol_table.len() as u8];
+        payload.extend_from_slice(&symbol_table);
+        module
+            .sections_mut()
+            .push(Section::Custom(CustomSection::new(
+                String::from("linking"),
+                payload,
+            )));
+        let contents = module.to_bytes().unwrap();
+
+        let names = read_names(&without_custom_section(&contents, "name")).unwrap();
+
+        assert_eq!(names.source, NamingSource::Linking);
+        assert_eq!(names.names.get(&0).map(String::as_str), Some("test"));
+    }
+
+    #[test]
+    fn falls_back_to_exports() {
+        let names = read_names(&test_module(vec![Instruction::End])).unwrap();
+
+        assert_eq!(names.source, NamingSource::Exports);
+        assert_eq!(names.names.get(&0).map(String::as_str), Some("main"));
+    }
+
+    #[test]
+    fn fails_when_no_function_is_named() {
+        let contents = ModuleBuilder::new()
+            .function()
+            .signature()
+            .build()
+            .body()
+            .build()
+            .build()
+            .build()
+            .to_bytes()
+            .unwrap();
+
+        let result = read_names(&contents);
+
+        assert!(matches!(result, Err(Error::NoFunctionNames)));
+    }
+}
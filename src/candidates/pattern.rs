@@ -0,0 +1,254 @@
+use crate::candidates::SymbolPath;
+use crate::Error;
+use regex::Regex;
+use std::fmt::{self, Display, Formatter};
+
+const REGEX_PREFIX: &str = "re:";
+
+/// Part of a symbol path which a pattern is matched against, selected by a prefix.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Target {
+    /// The whole name of the function.
+    Name,
+    /// `impl:`, the type on which a method is implemented.
+    ImplType,
+    /// `trait:`, the trait which a method implements.
+    Trait,
+    /// `closure:`, the function enclosing a closure.
+    Closure,
+    /// `crate:`, the crate in which the function is defined.
+    Crate,
+}
+
+const TARGET_PREFIXES: [(&str, Target); 4] = [
+    ("impl:", Target::ImplType),
+    ("trait:", Target::Trait),
+    ("closure:", Target::Closure),
+    ("crate:", Target::Crate),
+];
+
+/// A pattern selecting functions by their path.
+///
+/// A pattern starting with `re:` is a regular expression which may match anywhere in the name.
+/// Any other pattern is a glob matched against whole `::`-separated path segments, so that
+/// `roman_numerals` matches `roman_numerals::to_roman` but not `roman_numerals_extra::to_roman`.
+/// In a glob, `*` matches any part of a single segment, `?` a single character within a segment,
+/// and `**` any number of segments. A glob matches a name if it matches the name up to the end
+/// of one of its segments, so `roman_numerals::parse_*` matches every function of that module
+/// whose name starts with `parse_`, along with anything nested within those functions.
+///
+/// Names are matched without hashes, both as demangled (e.g.
+/// `<roman_numerals::Numeral as core::fmt::Display>::fmt`) and, for methods, through the type
+/// they are implemented on (`roman_numerals::Numeral::fmt`). A pattern may instead be prefixed
+/// with `impl:` to match the type of a method, `trait:` to match the trait it implements,
+/// `closure:` to select the closures within matching functions, or `crate:` to match the crate
+/// in which the function is defined.
+#[derive(Clone, Debug)]
+pub struct Pattern {
+    source: String,
+    target: Target,
+    regex: Regex,
+}
+
+impl Pattern {
+    pub fn new(source: &str) -> Result<Self, Error> {
+        let (target, pattern) = TARGET_PREFIXES
+            .iter()
+            .find_map(|(prefix, target)| source.strip_prefix(prefix).map(|rest| (*target, rest)))
+            .unwrap_or((Target::Name, source));
+        let expression = match pattern.strip_prefix(REGEX_PREFIX) {
+            Some(expression) => String::from(expression),
+            None => glob_to_regex(pattern),
+        };
+        let regex = Regex::new(&expression)
+            .map_err(|e| Error::InvalidPattern(String::from(source), e.to_string()))?;
+        Ok(Pattern {
+            source: String::from(source),
+            target,
+            regex,
+        })
+    }
+
+    pub fn matches(&self, path: &SymbolPath) -> bool {
+        match self.target {
+            Target::Name => self.matches_name(path),
+            Target::ImplType => self.matches_optional(path.impl_type_path()),
+            Target::Trait => self.matches_optional(path.trait_path()),
+            Target::Closure => path.closures > 0 && self.matches_name(&path.enclosing_function()),
+            Target::Crate => self.matches_optional(path.crate_name.clone()),
+        }
+    }
+
+    fn matches_name(&self, path: &SymbolPath) -> bool {
+        self.regex.is_match(&path.to_string()) || self.matches_optional(path.type_qualified_name())
+    }
+
+    fn matches_optional(&self, text: Option<String>) -> bool {
+        text.is_some_and(|text| self.regex.is_match(&text))
+    }
+}
+
+impl Display for Pattern {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.source)
+    }
+}
+
+fn glob_to_regex(glob: &str) -> String {
+    let mut expression = String::from("^");
+    let mut chars = glob.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '*' if chars.peek() == Some(&'*') => {
+                chars.next();
+                expression.push_str(".*");
+            }
+            '*' => expression.push_str("[^:]*"),
+            '?' => expression.push_str("[^:]"),
+            c => expression.push_str(&regex::escape(&c.to_string())),
+        }
+    }
+    expression.push_str("(?:$|::)");
+    expression
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::candidates::pattern::Pattern;
+    use crate::candidates::SymbolPath;
+    use crate::Error;
+
+    fn matches(pattern: &str, name: &str) -> bool {
+        Pattern::new(pattern)
+            .unwrap()
+            .matches(&SymbolPath::parse(name))
+    }
+
+    #[test]
+    fn plain_pattern_matches_whole_path_segments() {
+        assert!(matches("roman_numerals", "roman_numerals"));
+        assert!(matches(
+            "roman_numerals",
+            "roman_numerals::to_roman::h0123456789abcdef"
+        ));
+        assert!(!matches("roman_numerals", "roman_numerals_extra::to_roman"));
+        assert!(!matches(
+            "roman_numerals",
+            "other::roman_numerals::to_roman"
+        ));
+    }
+
+    #[test]
+    fn star_matches_within_a_single_segment() {
+        assert!(matches(
+            "roman_numerals::parse_*",
+            "roman_numerals::parse_digit::h0123456789abcdef"
+        ));
+        assert!(!matches(
+            "roman_numerals::parse_*",
+            "roman_numerals::to_roman"
+        ));
+        assert!(!matches("*::parse", "a::b::parse"));
+    }
+
+    #[test]
+    fn double_star_matches_any_number_of_segments() {
+        assert!(matches(
+            "**::tests",
+            "roman_numerals::inner::tests::it_works"
+        ));
+        assert!(!matches("**::tests", "roman_numerals::testsuite"));
+    }
+
+    #[test]
+    fn regex_pattern_matches_anywhere_in_the_name() {
+        let pattern = r"re:as core::fmt::Display>";
+
+        assert!(matches(
+            pattern,
+            "<roman_numerals::Roman as core::fmt::Display>::fmt"
+        ));
+        assert!(!matches(
+            pattern,
+            "<roman_numerals::Roman as core::fmt::Debug>::fmt"
+        ));
+    }
+
+    #[test]
+    fn plain_pattern_matches_methods_through_their_type() {
+        assert!(matches(
+            "roman_numerals",
+            "<roman_numerals::Roman as core::fmt::Display>::fmt"
+        ));
+        assert!(matches(
+            "roman_numerals::Roman::new",
+            "<roman_numerals::Roman>::new"
+        ));
+    }
+
+    #[test]
+    fn impl_pattern_matches_all_methods_of_a_type() {
+        assert!(matches(
+            "impl:roman_numerals::Roman",
+            "<roman_numerals::Roman<u8> as core::fmt::Display>::fmt"
+        ));
+        assert!(matches(
+            "impl:roman_numerals::Roman",
+            "roman_numerals::Roman::new::h0123456789abcdef"
+        ));
+        assert!(!matches(
+            "impl:roman_numerals::Roman",
+            "roman_numerals::to_roman"
+        ));
+    }
+
+    #[test]
+    fn trait_pattern_matches_impls_of_a_trait() {
+        assert!(matches(
+            "trait:core::fmt::*",
+            "<roman_numerals::Roman as core::fmt::Display>::fmt"
+        ));
+        assert!(!matches(
+            "trait:core::fmt::*",
+            "<roman_numerals::Roman>::new"
+        ));
+    }
+
+    #[test]
+    fn closure_pattern_matches_closures_within_a_function() {
+        assert!(matches(
+            "closure:roman_numerals::to_roman",
+            "roman_numerals::to_roman::{{closure}}::h0123456789abcdef"
+        ));
+        assert!(!matches(
+            "closure:roman_numerals::to_roman",
+            "roman_numerals::to_roman"
+        ));
+    }
+
+    #[test]
+    fn crate_pattern_matches_crate_of_function_or_impl() {
+        assert!(matches(
+            "crate:core",
+            "core::ptr::drop_in_place::<roman_numerals::Numeral>"
+        ));
+        assert!(matches(
+            "crate:alloc",
+            "<alloc::vec::Vec<roman_numerals::Numeral>>::push"
+        ));
+        assert!(matches("crate:core", "<&T as core::fmt::Display>::fmt"));
+        assert!(!matches(
+            "crate:core",
+            "<roman_numerals::Numeral as core::fmt::Display>::fmt"
+        ));
+    }
+
+    #[test]
+    fn fails_on_invalid_regex() {
+        let result = Pattern::new("re:(unclosed");
+
+        assert!(
+            matches!(result, Err(Error::InvalidPattern(pattern, _)) if pattern == "re:(unclosed")
+        );
+    }
+}
@@ -0,0 +1,416 @@
+use rustc_demangle::demangle;
+use std::fmt::{self, Display, Formatter};
+
+/// Structured path of a function, parsed from its demangled name.
+///
+/// Both legacy and v0 mangled names are understood. Hashes and crate disambiguators are dropped,
+/// as are the generic arguments of the function itself, so that all instances of a generic
+/// function share a path.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct SymbolPath {
+    /// Crate in which the function is defined, if the name reveals it. For trait impls, this is
+    /// the crate of the implementing type, or of the trait if the type is not a path.
+    pub crate_name: Option<String>,
+    /// Modules between the crate and the function, or the impl which contains it.
+    pub modules: Vec<String>,
+    /// Type on which the function is implemented, if it is a method, e.g.
+    /// `roman_numerals::Numeral`.
+    pub impl_type: Option<String>,
+    /// Trait which the method implements, if any, e.g. `core::fmt::Display`.
+    pub trait_name: Option<String>,
+    /// Name of the function, including any functions nested within a method which it belongs to.
+    pub function: String,
+    /// Number of nested closures in which the code lies, 0 for the body of the function itself.
+    pub closures: usize,
+}
+
+impl SymbolPath {
+    /// Demangles the given symbol name and parses the result. Names which are not mangled are
+    /// parsed as they are.
+    pub fn from_symbol(symbol: &str) -> Self {
+        Self::parse(&format!("{:#}", demangle(symbol)))
+    }
+
+    /// Parses an already demangled name, with or without its hash.
+    pub fn parse(demangled: &str) -> Self {
+        let mut segments: Vec<&str> = split_segments(demangled);
+        if segments.len() > 1 && is_legacy_hash(segments[segments.len() - 1]) {
+            segments.pop();
+        }
+        // Generic arguments of the function, written as a turbofish by v0 mangling.
+        let mut segments: Vec<&str> = segments
+            .iter()
+            .enumerate()
+            .filter(|(index, segment)| {
+                *index == 0 || !segment.starts_with('<') || segment.starts_with("<impl ")
+            })
+            .map(|(_, segment)| *segment)
+            .collect();
+        let closures = segments
+            .iter()
+            .rev()
+            .take_while(|segment| is_closure(segment))
+            .count();
+        segments.truncate(segments.len() - closures);
+
+        let mut path = SymbolPath {
+            closures,
+            ..SymbolPath::default()
+        };
+        match segments.first() {
+            Some(first) if first.starts_with('<') && first.ends_with('>') => {
+                path.set_impl(&first[1..first.len() - 1], " as ");
+                path.function = join_without_generics(&segments[1..]);
+            }
+            _ => match segments.iter().enumerate().find_map(|(position, segment)| {
+                let qualified = segment.strip_prefix("<impl ")?.strip_suffix('>')?;
+                Some((position, qualified))
+            }) {
+                // Legacy mangling names the module of an impl, followed by `<impl Trait for Type>`.
+                Some((position, qualified)) => {
+                    path.set_impl(qualified, " for ");
+                    path.crate_name = segments[..position].first().map(|s| String::from(*s));
+                    path.modules = segments[1.min(position)..position]
+                        .iter()
+                        .map(|s| String::from(*s))
+                        .collect();
+                    path.function = join_without_generics(&segments[position + 1..]);
+                }
+                None => path.set_item_path(&segments),
+            },
+        }
+        path
+    }
+
+    /// Sets the impl from the text within the angle brackets of a qualified path, which is either
+    /// a type alone or a trait and a type separated by the given keyword.
+    fn set_impl(&mut self, qualified: &str, separator: &str) {
+        let (impl_type, trait_name) = match split_top_level(qualified, separator) {
+            Some((left, right)) if separator == " as " => (left, Some(right)),
+            Some((left, right)) => (right, Some(left)),
+            None => (qualified, None),
+        };
+        self.impl_type = Some(String::from(impl_type));
+        self.trait_name = trait_name.map(String::from);
+        let owner = if is_path(impl_type) {
+            Some(impl_type)
+        } else {
+            trait_name.filter(|name| is_path(name))
+        };
+        if let Some(owner) = owner {
+            let segments = split_segments(owner);
+            if segments.len() > 1 {
+                self.crate_name = Some(String::from(strip_generics(segments[0])));
+                if owner == impl_type {
+                    self.modules = segments[1..segments.len() - 1]
+                        .iter()
+                        .map(|s| String::from(*s))
+                        .collect();
+                }
+            }
+        }
+    }
+
+    /// Sets the crate, modules and function from a plain path. Legacy mangling writes inherent
+    /// methods as plain paths, so a capitalized segment just before the function is taken to be
+    /// the type of an impl.
+    fn set_item_path(&mut self, segments: &[&str]) {
+        let (function, rest) = match segments.split_last() {
+            Some(split) => split,
+            None => return,
+        };
+        self.function = String::from(strip_generics(function));
+        if let Some((crate_name, mut modules)) = rest.split_first() {
+            self.crate_name = Some(String::from(*crate_name));
+            if let Some((last, parents)) = modules.split_last() {
+                if last.starts_with(|c: char| c.is_ascii_uppercase()) {
+                    self.impl_type = Some(rest.join("::"));
+                    modules = parents;
+                }
+            }
+            self.modules = modules.iter().map(|s| String::from(*s)).collect();
+        }
+    }
+
+    /// Path of the type on which the function is implemented, without generic arguments.
+    pub fn impl_type_path(&self) -> Option<String> {
+        self.impl_type
+            .as_deref()
+            .filter(|impl_type| is_path(impl_type))
+            .map(strip_path_generics)
+    }
+
+    /// Path of the trait which the method implements, without generic arguments.
+    pub fn trait_path(&self) -> Option<String> {
+        self.trait_name.as_deref().map(strip_path_generics)
+    }
+
+    /// Path of the function through the type it is implemented on, e.g.
+    /// `roman_numerals::Numeral::fmt` for `<roman_numerals::Numeral as core::fmt::Display>::fmt`,
+    /// if the function is a method of a type with a path.
+    pub fn type_qualified_name(&self) -> Option<String> {
+        self.impl_type_path().map(|impl_type| {
+            let mut name = format!("{}::{}", impl_type, self.function);
+            for _ in 0..self.closures {
+                name.push_str("::{closure}");
+            }
+            name
+        })
+    }
+
+    /// The path without any closures, i.e. that of the function which contains the code.
+    pub fn enclosing_function(&self) -> SymbolPath {
+        SymbolPath {
+            closures: 0,
+            ..self.clone()
+        }
+    }
+}
+
+impl Display for SymbolPath {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match (&self.impl_type, &self.trait_name) {
+            (Some(impl_type), Some(trait_name)) => write!(f, "<{} as {}>", impl_type, trait_name)?,
+            (Some(impl_type), None) if is_path(impl_type) => f.write_str(impl_type)?,
+            (Some(impl_type), None) => write!(f, "<{}>", impl_type)?,
+            (None, _) => {
+                if let Some(crate_name) = &self.crate_name {
+                    f.write_str(crate_name)?;
+                }
+                for module in &self.modules {
+                    write!(f, "::{}", module)?;
+                }
+            }
+        }
+        if self.impl_type.is_some() || self.crate_name.is_some() {
+            f.write_str("::")?;
+        }
+        f.write_str(&self.function)?;
+        for _ in 0..self.closures {
+            f.write_str("::{closure}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Splits a path at each `::` which is not nested within angle brackets.
+fn split_segments(path: &str) -> Vec<&str> {
+    let mut segments = Vec::new();
+    let mut depth = 0;
+    let mut start = 0;
+    let bytes = path.as_bytes();
+    let mut index = 0;
+    while index < bytes.len() {
+        match bytes[index] {
+            b'<' => depth += 1,
+            b'>' if index == 0 || bytes[index - 1] != b'-' => depth -= 1,
+            b':' if depth == 0 && bytes.get(index + 1) == Some(&b':') => {
+                segments.push(&path[start..index]);
+                start = index + 2;
+                index += 1;
+            }
+            _ => {}
+        }
+        index += 1;
+    }
+    segments.push(&path[start..]);
+    segments
+}
+
+/// Splits the text at the first occurrence of the separator which is not nested within angle
+/// brackets.
+fn split_top_level<'a>(text: &'a str, separator: &str) -> Option<(&'a str, &'a str)> {
+    let mut depth = 0;
+    for (index, c) in text.char_indices() {
+        match c {
+            '<' => depth += 1,
+            '>' if !text[..index].ends_with('-') => depth -= 1,
+            _ if depth == 0 && text[index..].starts_with(separator) => {
+                return Some((&text[..index], &text[index + separator.len()..]));
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+fn strip_path_generics(path: &str) -> String {
+    join_without_generics(&split_segments(path))
+}
+
+fn join_without_generics(segments: &[&str]) -> String {
+    segments
+        .iter()
+        .map(|segment| strip_generics(segment))
+        .collect::<Vec<_>>()
+        .join("::")
+}
+
+fn strip_generics(segment: &str) -> &str {
+    segment.split('<').next().unwrap_or(segment)
+}
+
+fn is_path(text: &str) -> bool {
+    text.starts_with(|c: char| c.is_alphabetic() || c == '_')
+        && !text.starts_with("dyn ")
+        && !text.starts_with("fn(")
+        && !text.starts_with("impl ")
+}
+
+fn is_closure(segment: &str) -> bool {
+    segment == "{{closure}}" || segment.starts_with("{closure#")
+}
+
+fn is_legacy_hash(segment: &str) -> bool {
+    segment.len() == 17
+        && segment.starts_with('h')
+        && segment[1..].chars().all(|c| c.is_ascii_hexdigit())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::candidates::symbol::SymbolPath;
+
+    #[test]
+    fn parses_free_function_and_strips_legacy_hash() {
+        let path = SymbolPath::from_symbol("_ZN14roman_numerals8to_roman17h0123456789abcdefE");
+
+        assert_eq!(path.crate_name.as_deref(), Some("roman_numerals"));
+        assert!(path.modules.is_empty());
+        assert_eq!(path.function, "to_roman");
+        assert_eq!(path.to_string(), "roman_numerals::to_roman");
+    }
+
+    #[test]
+    fn parses_legacy_closure() {
+        let path = SymbolPath::from_symbol(
+            "_ZN14roman_numerals5tests18it_converts_1_to_i28_$u7b$$u7b$closure$u7d$$u7d$\
+             17h181c9662b9d4a25aE",
+        );
+
+        assert_eq!(path.modules, vec!["tests"]);
+        assert_eq!(path.function, "it_converts_1_to_i");
+        assert_eq!(path.closures, 1);
+        assert_eq!(
+            path.to_string(),
+            "roman_numerals::tests::it_converts_1_to_i::{closure}"
+        );
+    }
+
+    #[test]
+    fn parses_trait_impl() {
+        let path = SymbolPath::parse(
+            "<roman_numerals::numeral::Numeral as core::fmt::Display>::fmt::h0123456789abcdef",
+        );
+
+        assert_eq!(path.crate_name.as_deref(), Some("roman_numerals"));
+        assert_eq!(path.modules, vec!["numeral"]);
+        assert_eq!(
+            path.impl_type.as_deref(),
+            Some("roman_numerals::numeral::Numeral")
+        );
+        assert_eq!(path.trait_name.as_deref(), Some("core::fmt::Display"));
+        assert_eq!(path.function, "fmt");
+        assert_eq!(
+            path.type_qualified_name().as_deref(),
+            Some("roman_numerals::numeral::Numeral::fmt")
+        );
+    }
+
+    #[test]
+    fn parses_legacy_impl_segment() {
+        let path = SymbolPath::from_symbol(
+            "_ZN4core3str6traits66_$LT$impl$u20$core..ops..index..Index$LT$I$GT$$u20$for$u20$\
+             str$GT$5index17h144cd7e17d134fe1E",
+        );
+
+        assert_eq!(path.crate_name.as_deref(), Some("core"));
+        assert_eq!(path.modules, vec!["str", "traits"]);
+        assert_eq!(path.impl_type.as_deref(), Some("str"));
+        assert_eq!(
+            path.trait_name.as_deref(),
+            Some("core::ops::index::Index<I>")
+        );
+        assert_eq!(path.function, "index");
+    }
+
+    #[test]
+    fn parses_unterminated_impl_segment_as_plain_path() {
+        let path = SymbolPath::parse("a::<impl ");
+
+        assert_eq!(path.impl_type, None);
+        assert_eq!(path.trait_name, None);
+        assert_eq!(path.crate_name.as_deref(), Some("a"));
+    }
+
+    #[test]
+    fn parses_legacy_inherent_method() {
+        let path = SymbolPath::parse("roman_numerals::Numeral::new::h0123456789abcdef");
+
+        assert_eq!(path.impl_type.as_deref(), Some("roman_numerals::Numeral"));
+        assert_eq!(path.trait_name, None);
+        assert_eq!(path.function, "new");
+        assert_eq!(path.to_string(), "roman_numerals::Numeral::new");
+    }
+
+    #[test]
+    fn parses_v0_inherent_method_of_generic_type() {
+        let path = SymbolPath::parse("<roman_numerals::inner::W<u8>>::get");
+
+        assert_eq!(path.crate_name.as_deref(), Some("roman_numerals"));
+        assert_eq!(path.modules, vec!["inner"]);
+        assert_eq!(
+            path.impl_type.as_deref(),
+            Some("roman_numerals::inner::W<u8>")
+        );
+        assert_eq!(path.function, "get");
+        assert_eq!(
+            path.type_qualified_name().as_deref(),
+            Some("roman_numerals::inner::W::get")
+        );
+    }
+
+    #[test]
+    fn parses_v0_closure_and_drops_function_generic_arguments() {
+        let closure = SymbolPath::parse("roman_numerals::to_roman::{closure#0}");
+        let generic =
+            SymbolPath::parse("<core::fmt::rt::Argument>::new_display::<roman_numerals::Numeral>");
+
+        assert_eq!(closure.function, "to_roman");
+        assert_eq!(closure.closures, 1);
+        assert_eq!(
+            generic.impl_type.as_deref(),
+            Some("core::fmt::rt::Argument")
+        );
+        assert_eq!(generic.function, "new_display");
+        assert_eq!(generic.to_string(), "core::fmt::rt::Argument::new_display");
+    }
+
+    #[test]
+    fn attributes_impl_on_non_path_type_to_crate_of_trait() {
+        let path = SymbolPath::parse("<&T as core::fmt::Debug>::fmt");
+
+        assert_eq!(path.crate_name.as_deref(), Some("core"));
+        assert!(path.modules.is_empty());
+        assert_eq!(path.type_qualified_name(), None);
+        assert_eq!(path.to_string(), "<&T as core::fmt::Debug>::fmt");
+    }
+
+    #[test]
+    fn drops_generic_arguments_written_in_function_segment() {
+        let path = SymbolPath::parse("core::ptr::drop_in_place<roman_numerals::Numeral>");
+
+        assert_eq!(path.crate_name.as_deref(), Some("core"));
+        assert_eq!(path.modules, vec!["ptr"]);
+        assert_eq!(path.function, "drop_in_place");
+    }
+
+    #[test]
+    fn parses_unmangled_name() {
+        let path = SymbolPath::from_symbol("__wasm_call_ctors");
+
+        assert_eq!(path.crate_name, None);
+        assert_eq!(path.function, "__wasm_call_ctors");
+        assert_eq!(path.to_string(), "__wasm_call_ctors");
+    }
+}
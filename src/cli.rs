@@ -0,0 +1,254 @@
+//! Command-line options shared by the `mutation-test-engine` binary and `cargo mutate`.
+
+use crate::{engine, mutation, Config, Error, MutationTestRun, RunnerOptions, Threshold};
+use clap::Args;
+use std::path::PathBuf;
+
+/// Options selecting the mutants and controlling how they are run, which flags give and the
+/// configuration file fills in (see [`CommonOptions::merge`]).
+#[derive(Args, Clone, Debug)]
+pub struct CommonOptions {
+    /// Read settings from this file instead of the nearest mutation-test.toml in the working
+    /// directory or its ancestors; flags take precedence over its settings
+    #[arg(long, value_name = "FILE")]
+    pub config: Option<PathBuf>,
+
+    /// Only mutate functions whose demangled name matches this pattern: a glob over `::`-separated
+    /// path segments, or a regular expression prefixed with `re:` (repeatable)
+    #[arg(short, long = "include", value_name = "PATTERN")]
+    pub include: Vec<String>,
+
+    /// Never mutate functions whose demangled name matches this pattern (repeatable)
+    #[arg(short, long = "exclude", value_name = "PATTERN")]
+    pub exclude: Vec<String>,
+
+    /// Leave out drop glue and standard library code, as by default
+    #[arg(long, overrides_with = "no_default_excludes")]
+    pub default_excludes: bool,
+
+    /// Also mutate drop glue and standard library code, which is excluded by default
+    #[arg(long, overrides_with = "default_excludes")]
+    pub no_default_excludes: bool,
+
+    /// Leave out code annotated with `// mutants: skip` or `#[mutants::skip]`, as by default
+    #[arg(long, overrides_with = "no_skip_annotations")]
+    pub skip_annotations: bool,
+
+    /// Also mutate code annotated with `// mutants: skip` or `#[mutants::skip]`
+    #[arg(long, overrides_with = "skip_annotations")]
+    pub no_skip_annotations: bool,
+
+    /// Only mutate code on the lines added or changed by this unified diff
+    #[arg(long, value_name = "FILE", conflicts_with = "git_base")]
+    pub diff: Option<PathBuf>,
+
+    /// Only mutate code on the lines changed since this git revision
+    #[arg(long, value_name = "REV")]
+    pub git_base: Option<String>,
+
+    /// Only apply the mutators with this ID or kind, as listed by `list-mutators` (repeatable;
+    /// the mutators enabled by default are applied otherwise)
+    #[arg(short, long = "mutator", value_name = "ID", value_parser = mutation::mutator_name)]
+    pub mutator: Vec<String>,
+
+    /// Do not apply the mutators with this ID or kind, even if enabled (repeatable)
+    #[arg(long = "disable-mutator", value_name = "ID", value_parser = mutation::mutator_name)]
+    pub disable_mutator: Vec<String>,
+
+    /// Only run the mutant with this ID, as shown in the reports (repeatable)
+    #[arg(long = "mutant", value_name = "ID")]
+    pub mutant: Vec<String>,
+
+    /// File listing surviving mutants which are accepted, by ID
+    #[arg(long, value_name = "FILE")]
+    pub accepted: Option<PathBuf>,
+
+    /// Fail with exit code 3 if the mutation score is below this percentage, either of the
+    /// functions matching a pattern given as PATTERN=PERCENT, or of all other functions if only
+    /// PERCENT is given (repeatable)
+    #[arg(long, value_name = "[PATTERN=]PERCENT")]
+    pub min_score: Vec<Threshold>,
+
+    /// Exported function which runs the tests [default: main]
+    #[arg(long, value_name = "NAME")]
+    pub entry_point: Option<String>,
+
+    /// Report a mutant as timed out once it executes this many times the instructions executed by
+    /// the original tests [default: 10]
+    #[arg(long, value_name = "FACTOR")]
+    pub timeout_factor: Option<u64>,
+
+    /// Report a mutant as timed out once it executes this many instructions, instead of deriving
+    /// the limit from the original tests
+    #[arg(long, value_name = "INSTRUCTIONS", conflicts_with = "timeout_factor")]
+    pub fuel: Option<u64>,
+
+    /// Number of mutants to run at once [default: number of CPU cores]
+    #[arg(short, long, value_name = "N")]
+    pub jobs: Option<usize>,
+
+    /// Show how each surviving mutant changed the code of its function in the text report
+    #[arg(long, overrides_with = "no_show_diffs")]
+    pub show_diffs: bool,
+
+    /// Do not show how surviving mutants changed the code, as by default
+    #[arg(long, overrides_with = "show_diffs")]
+    pub no_show_diffs: bool,
+}
+
+impl CommonOptions {
+    /// Fills in the settings which were not given as flags from the configuration file. The
+    /// `format` and `output` report settings are left to the caller.
+    pub fn merge(&mut self, config: &Config) -> Result<(), Error> {
+        let Config {
+            candidates,
+            mutators,
+            runner,
+            thresholds,
+            report,
+        } = config;
+        if self.include.is_empty() {
+            self.include = candidates.include.clone();
+        }
+        if self.exclude.is_empty() {
+            self.exclude = candidates.exclude.clone();
+        }
+        // The flags of each pair override each other, so at most one of them is set, and the
+        // file only decides if neither is.
+        self.default_excludes = (self.default_excludes || self.no_default_excludes)
+            .then_some(self.default_excludes)
+            .or(candidates.default_excludes)
+            .unwrap_or(true);
+        self.skip_annotations = (self.skip_annotations || self.no_skip_annotations)
+            .then_some(self.skip_annotations)
+            .or(candidates.skip_annotations)
+            .unwrap_or(true);
+        if self.mutator.is_empty() {
+            for name in &mutators.enable {
+                self.mutator.push(mutation::mutator_name(name)?);
+            }
+        }
+        if self.disable_mutator.is_empty() {
+            for name in &mutators.disable {
+                self.disable_mutator.push(mutation::mutator_name(name)?);
+            }
+        }
+        if self.entry_point.is_none() {
+            self.entry_point = runner.entry_point.clone();
+        }
+        if self.fuel.is_none() && self.timeout_factor.is_none() {
+            self.fuel = runner.fuel;
+        }
+        self.timeout_factor = self.timeout_factor.or(runner.timeout_factor);
+        self.jobs = self.jobs.or(runner.jobs);
+        if self.min_score.is_empty() {
+            self.min_score = thresholds.to_thresholds()?;
+        }
+        self.show_diffs = (self.show_diffs || self.no_show_diffs)
+            .then_some(self.show_diffs)
+            .or(report.show_diffs)
+            .unwrap_or(false);
+        if self.accepted.is_none() {
+            self.accepted = report.accepted.clone();
+        }
+        Ok(())
+    }
+
+    /// Applies the settings which do not depend on the binary being run, i.e. all but the
+    /// include patterns, the changed lines and the accepted mutants, to the given run.
+    pub fn configure<'a>(&self, run: MutationTestRun<'a>) -> MutationTestRun<'a> {
+        run.exclude_modules(&self.exclude)
+            .default_excludes(self.default_excludes)
+            .skip_annotations(self.skip_annotations)
+            .enable_mutators(&self.mutator)
+            .disable_mutators(&self.disable_mutator)
+            .select_mutants(&self.mutant)
+            .runner_options(RunnerOptions {
+                entry_point: self
+                    .entry_point
+                    .clone()
+                    .unwrap_or_else(|| RunnerOptions::default().entry_point),
+                fuel: self.fuel,
+            })
+            .timeout_factor(
+                self.timeout_factor
+                    .unwrap_or(engine::DEFAULT_TIMEOUT_FACTOR),
+            )
+            .jobs(self.jobs.unwrap_or_else(engine::default_jobs))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::cli::CommonOptions;
+    use crate::{Config, Error};
+    use clap::Parser;
+
+    #[derive(Parser)]
+    struct Command {
+        #[command(flatten)]
+        options: CommonOptions,
+    }
+
+    fn merged(args: &[&str], config: &str) -> Result<CommonOptions, Error> {
+        let mut options =
+            Command::try_parse_from(std::iter::once("test").chain(args.iter().copied()))
+                .unwrap()
+                .options;
+        options.merge(&Config::parse(config).unwrap())?;
+        Ok(options)
+    }
+
+    #[test]
+    fn fills_in_settings_from_config() {
+        let options = merged(
+            &[],
+            "[candidates]\ninclude = [\"roman_numerals\"]\nskip_annotations = false\n\
+             [mutators]\nenable = [\"InstructionSwapping\"]\n\
+             [runner]\nentry_point = \"run\"\nfuel = 1000\njobs = 2\n\
+             [report]\nshow_diffs = true\n",
+        )
+        .unwrap();
+
+        assert_eq!(options.include, vec!["roman_numerals"]);
+        assert!(options.default_excludes);
+        assert!(!options.skip_annotations);
+        assert_eq!(options.mutator, vec!["InstructionSwapping"]);
+        assert_eq!(options.entry_point.as_deref(), Some("run"));
+        assert_eq!(options.fuel, Some(1000));
+        assert_eq!(options.jobs, Some(2));
+        assert!(options.show_diffs);
+    }
+
+    #[test]
+    fn flags_take_precedence_over_config() {
+        let options = merged(
+            &[
+                "--include",
+                "parser",
+                "--skip-annotations",
+                "--timeout-factor",
+                "5",
+                "--no-show-diffs",
+            ],
+            "[candidates]\ninclude = [\"roman_numerals\"]\nskip_annotations = false\n\
+             [runner]\nfuel = 1000\n\
+             [report]\nshow_diffs = true\n",
+        )
+        .unwrap();
+
+        assert_eq!(options.include, vec!["parser"]);
+        assert!(options.skip_annotations);
+        assert_eq!(options.timeout_factor, Some(5));
+        assert_eq!(options.fuel, None);
+        assert!(!options.show_diffs);
+    }
+
+    #[test]
+    fn rejects_unknown_mutator_in_config() {
+        assert!(matches!(
+            merged(&[], "[mutators]\ndisable = [\"no-such-mutator\"]\n"),
+            Err(Error::UnknownMutator(name)) if name == "no-such-mutator"
+        ));
+    }
+}
@@ -0,0 +1,245 @@
+//! Project configuration read from a `mutation-test.toml` file.
+//!
+//! Every setting is optional, and settings given on the command line take precedence:
+//!
+//! ```toml
+//! [candidates]
+//! include = ["roman_numerals"]
+//! exclude = ["roman_numerals::tests"]
+//! default_excludes = true
+//! skip_annotations = true
+//!
+//! [mutators]
+//! enable = ["InstructionSwapping", "if-condition-to-false"]
+//! disable = ["swap-f64-rounding"]
+//!
+//! [runner]
+//! entry_point = "main"
+//! timeout_factor = 10
+//! jobs = 4
+//!
+//! [thresholds]
+//! minimum = 70
+//! modules = { "roman_numerals::parser" = 90 }
+//!
+//! [report]
+//! format = "html"
+//! output = "target/mutants.html"
+//! show_diffs = true
+//! accepted = "accepted-mutants.toml"
+//! ```
+//!
+//! Relative paths are relative to the directory containing the file.
+
+use crate::{Error, Threshold};
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Name of the configuration file, looked up in the working directory and its ancestors.
+pub const CONFIG_FILE_NAME: &str = "mutation-test.toml";
+
+#[derive(Clone, Debug, Default, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    pub candidates: CandidatesConfig,
+    pub mutators: MutatorsConfig,
+    pub runner: RunnerConfig,
+    pub thresholds: ThresholdsConfig,
+    pub report: ReportConfig,
+}
+
+/// Selection of the functions to mutate (see [`crate::MutationTestRun::include_module`]).
+#[derive(Clone, Debug, Default, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct CandidatesConfig {
+    pub include: Vec<String>,
+    pub exclude: Vec<String>,
+    pub default_excludes: Option<bool>,
+    pub skip_annotations: Option<bool>,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct MutatorsConfig {
+    /// IDs or kinds of the mutators to apply, those enabled by default if empty (see
+    /// [`crate::mutation::mutator_names`]).
+    pub enable: Vec<String>,
+    /// IDs or kinds of the mutators not to apply.
+    pub disable: Vec<String>,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct RunnerConfig {
+    pub entry_point: Option<String>,
+    pub fuel: Option<u64>,
+    pub timeout_factor: Option<u64>,
+    pub jobs: Option<usize>,
+}
+
+/// Minimum mutation scores as percentages (see [`crate::threshold`]).
+#[derive(Clone, Debug, Default, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ThresholdsConfig {
+    /// Minimum score of the mutants of the functions matched by none of the patterns.
+    pub minimum: Option<f64>,
+    /// Minimum scores of the mutants of the functions matched by each pattern.
+    pub modules: BTreeMap<String, f64>,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ReportConfig {
+    /// Name of the format of the report, e.g. `json`.
+    pub format: Option<String>,
+    pub output: Option<PathBuf>,
+    pub show_diffs: Option<bool>,
+    /// File listing the accepted surviving mutants (see [`crate::accepted`]).
+    pub accepted: Option<PathBuf>,
+}
+
+impl ThresholdsConfig {
+    /// The minimum mutation scores, those for patterns first.
+    pub fn to_thresholds(&self) -> Result<Vec<Threshold>, Error> {
+        self.modules
+            .iter()
+            .map(|(pattern, minimum)| (Some(pattern.as_str()), *minimum))
+            .chain(self.minimum.map(|minimum| (None, minimum)))
+            .map(|(pattern, minimum)| Threshold::new(pattern, minimum))
+            .collect()
+    }
+}
+
+impl Config {
+    pub fn parse(text: &str) -> Result<Self, Error> {
+        toml::from_str(text).map_err(|e| Error::Config(e.to_string()))
+    }
+
+    /// Reads the configuration file at the given path, resolving the paths it contains against
+    /// the directory containing it.
+    pub fn read(path: &Path) -> Result<Self, Error> {
+        let text = fs::read_to_string(path)
+            .map_err(|e| Error::Config(format!("could not read {}: {}", path.display(), e)))?;
+        let mut config = Self::parse(&text).map_err(|e| match e {
+            Error::Config(message) => Error::Config(format!("{}: {}", path.display(), message)),
+            e => e,
+        })?;
+        let directory = path.parent().unwrap_or_else(|| Path::new(""));
+        let report = &mut config.report;
+        for path in report.output.iter_mut().chain(report.accepted.iter_mut()) {
+            *path = directory.join(&*path);
+        }
+        Ok(config)
+    }
+
+    /// Reads the given configuration file, or else the nearest one in the given working directory
+    /// or its ancestors. Without either, all settings take their defaults.
+    pub fn load(explicit: Option<&Path>, working_directory: &Path) -> Result<Self, Error> {
+        match explicit {
+            Some(path) => Config::read(path),
+            None => Ok(Config::discover(working_directory)?
+                .map(|(_, config)| config)
+                .unwrap_or_default()),
+        }
+    }
+
+    /// Reads the nearest configuration file in the given directory or its ancestors, if there is
+    /// one. Returns its path along with the configuration.
+    pub fn discover(directory: &Path) -> Result<Option<(PathBuf, Self)>, Error> {
+        directory
+            .ancestors()
+            .map(|directory| directory.join(CONFIG_FILE_NAME))
+            .find(|path| path.is_file())
+            .map(|path| Config::read(&path).map(|config| (path, config)))
+            .transpose()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::config::{Config, CONFIG_FILE_NAME};
+    use crate::Error;
+    use std::fs;
+    use std::path::Path;
+
+    #[test]
+    fn parses_all_sections() {
+        let config = Config::parse(
+            r#"
+[candidates]
+include = ["roman_numerals"]
+default_excludes = false
+
+[mutators]
+enable = ["IfConditionToTrue"]
+disable = ["swap-i32-bitwise"]
+
+[runner]
+timeout_factor = 5
+
+[thresholds]
+minimum = 70
+modules = { "roman_numerals::parser" = 90 }
+
+[report]
+format = "json"
+"#,
+        )
+        .unwrap();
+
+        assert_eq!(config.candidates.include, ["roman_numerals"]);
+        assert_eq!(config.candidates.default_excludes, Some(false));
+        assert_eq!(config.candidates.skip_annotations, None);
+        assert_eq!(config.mutators.enable, ["IfConditionToTrue"]);
+        assert_eq!(config.mutators.disable, ["swap-i32-bitwise"]);
+        assert_eq!(config.runner.timeout_factor, Some(5));
+        assert_eq!(config.report.format.as_deref(), Some("json"));
+        let thresholds: Vec<String> = config
+            .thresholds
+            .to_thresholds()
+            .unwrap()
+            .iter()
+            .map(ToString::to_string)
+            .collect();
+        assert_eq!(thresholds, ["roman_numerals::parser=90", "70"]);
+    }
+
+    #[test]
+    fn rejects_unknown_settings() {
+        let result = Config::parse("[runner]\ntimeout = 5\n");
+
+        assert!(matches!(result, Err(Error::Config(_))));
+    }
+
+    #[test]
+    fn discovers_file_in_ancestor_directory() {
+        let root =
+            std::env::temp_dir().join(format!("mutation-test-config-{}", std::process::id()));
+        let nested = root.join("crates").join("parser");
+        fs::create_dir_all(&nested).unwrap();
+        fs::write(
+            root.join(CONFIG_FILE_NAME),
+            "[report]\naccepted = \"accepted.toml\"\n",
+        )
+        .unwrap();
+
+        let discovered = Config::discover(&nested);
+        let explicit = Config::load(
+            Some(&root.join(CONFIG_FILE_NAME)),
+            Path::new("/nonexistent"),
+        );
+        fs::remove_dir_all(&root).unwrap();
+
+        let (path, config) = discovered.unwrap().unwrap();
+        assert_eq!(path, root.join(CONFIG_FILE_NAME));
+        assert_eq!(config.report.accepted, Some(root.join("accepted.toml")));
+        assert_eq!(explicit.unwrap(), config);
+        assert_eq!(Config::discover(Path::new("/nonexistent")).unwrap(), None);
+        assert_eq!(
+            Config::load(None, Path::new("/nonexistent")).unwrap(),
+            Config::default()
+        );
+    }
+}
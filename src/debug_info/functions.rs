@@ -0,0 +1,274 @@
+use crate::debug_info::lines::file_path;
+use crate::debug_info::{code_body_ranges, instruction_offsets, load_dwarf, Dwarf, Slice};
+use crate::SourceLocation;
+use gimli::{AttributeValue, DwAt, UnitOffset};
+use parity_wasm::elements::Module;
+use std::ops::Range;
+use std::path::PathBuf;
+
+/// Number of `DW_AT_abstract_origin` or `DW_AT_specification` references followed to find an
+/// attribute of a function, which guards against cycles in malformed debugging information.
+const MAX_ORIGIN_DEPTH: usize = 4;
+
+/// A function described by the `.debug_info` section whose code is in the module.
+pub(crate) struct Subprogram {
+    pub(crate) body_index: usize,
+    /// Linkage name of the function, or its plain name if the compiler recorded none.
+    pub(crate) name: Option<String>,
+    /// Line on which the function is declared, without a column.
+    pub(crate) declaration: Option<SourceLocation>,
+}
+
+/// Reads the subprograms of the DWARF debugging information, locating each function by the offset
+/// of its code within the code section. The module must have been parsed from the given contents.
+pub(crate) fn subprograms(module: &Module, module_contents: &[u8]) -> Vec<Subprogram> {
+    let body_ranges = match code_body_ranges(module_contents) {
+        Some(ranges) => ranges,
+        None => return Vec::new(),
+    };
+    let dwarf = match load_dwarf(module) {
+        Some(dwarf) => dwarf,
+        None => return Vec::new(),
+    };
+
+    let mut subprograms = Vec::new();
+    let mut units = dwarf.units();
+    while let Ok(Some(header)) = units.next() {
+        let unit = match dwarf.unit(header) {
+            Ok(unit) => unit,
+            Err(_) => continue,
+        };
+        let mut entries = unit.entries();
+        while let Ok(Some((_, entry))) = entries.next_dfs() {
+            if entry.tag() != gimli::DW_TAG_subprogram {
+                continue;
+            }
+            let low_pc = match entry.attr_value(gimli::DW_AT_low_pc) {
+                Ok(Some(value)) => dwarf.attr_address(&unit, value).ok().flatten(),
+                _ => None,
+            };
+            let body_index = match low_pc.and_then(|low_pc| {
+                body_ranges
+                    .iter()
+                    .position(|range| range.contains(&(low_pc as usize)))
+            }) {
+                Some(body_index) => body_index,
+                None => continue,
+            };
+            let offset = entry.offset();
+            subprograms.push(Subprogram {
+                body_index,
+                name: name(&dwarf, &unit, offset),
+                declaration: declaration(&dwarf, &unit, offset),
+            });
+        }
+    }
+    subprograms
+}
+
+/// A function whose code the compiler copied into a function of the module.
+pub(crate) struct InlinedFunction {
+    /// Linkage name of the inlined function, or its plain name if the compiler recorded none.
+    pub(crate) name: String,
+    /// Line on which the inlined function is declared, without a column.
+    pub(crate) declaration: Option<SourceLocation>,
+    /// Ranges of the code compiled from the inlined function, relative to the start of the
+    /// contents of the code section.
+    ranges: Vec<Range<u64>>,
+}
+
+/// Maps instructions to the inlined functions they were compiled from, using the inlined
+/// subroutines of the `.debug_info` section.
+pub(crate) struct InlinedCode {
+    /// Inlined functions of each function body, each listed after those it is nested in.
+    functions: Vec<Vec<InlinedFunction>>,
+    instruction_offsets: Vec<Vec<usize>>,
+}
+
+impl InlinedCode {
+    /// Reads the inlined subroutines of the module, which must have been parsed from the given
+    /// contents. Returns `None` if the module has no debugging information on inlined code.
+    pub(crate) fn new(module: &Module, module_contents: &[u8]) -> Option<Self> {
+        let body_ranges = code_body_ranges(module_contents)?;
+        let dwarf = load_dwarf(module)?;
+        let mut functions: Vec<Vec<InlinedFunction>> =
+            body_ranges.iter().map(|_| Vec::new()).collect();
+        let mut found = false;
+        let mut units = dwarf.units();
+        while let Ok(Some(header)) = units.next() {
+            let unit = match dwarf.unit(header) {
+                Ok(unit) => unit,
+                Err(_) => continue,
+            };
+            let mut entries = unit.entries();
+            while let Ok(Some((_, entry))) = entries.next_dfs() {
+                if entry.tag() != gimli::DW_TAG_inlined_subroutine {
+                    continue;
+                }
+                let mut ranges = Vec::new();
+                if let Ok(mut die_ranges) = dwarf.die_ranges(&unit, entry) {
+                    while let Ok(Some(range)) = die_ranges.next() {
+                        // Code of functions discarded by the linker starts at address 0.
+                        if range.begin > 0 && range.begin < range.end {
+                            ranges.push(range.begin..range.end);
+                        }
+                    }
+                }
+                let body_index = match ranges.first().and_then(|range| {
+                    body_ranges
+                        .iter()
+                        .position(|body| body.contains(&(range.start as usize)))
+                }) {
+                    Some(body_index) => body_index,
+                    None => continue,
+                };
+                let offset = entry.offset();
+                if let Some(name) = name(&dwarf, &unit, offset) {
+                    functions[body_index].push(InlinedFunction {
+                        name,
+                        declaration: declaration(&dwarf, &unit, offset),
+                        ranges,
+                    });
+                    found = true;
+                }
+            }
+        }
+        if !found {
+            return None;
+        }
+        Some(InlinedCode {
+            functions,
+            instruction_offsets: instruction_offsets(module_contents)?,
+        })
+    }
+
+    /// Returns the innermost inlined function from which the given instruction of the given
+    /// function body was compiled, if any.
+    pub(crate) fn function_at(
+        &self,
+        body_index: usize,
+        instruction_index: usize,
+    ) -> Option<&InlinedFunction> {
+        let offset = *self
+            .instruction_offsets
+            .get(body_index)?
+            .get(instruction_index)? as u64;
+        self.functions
+            .get(body_index)?
+            .iter()
+            .rev()
+            .find(|function| function.ranges.iter().any(|range| range.contains(&offset)))
+    }
+
+    /// Returns the functions inlined into the given function body.
+    pub(crate) fn functions_in(&self, body_index: usize) -> &[InlinedFunction] {
+        self.functions.get(body_index).map_or(&[], Vec::as_slice)
+    }
+}
+
+fn name(dwarf: &Dwarf, unit: &gimli::Unit<Slice>, offset: UnitOffset) -> Option<String> {
+    [gimli::DW_AT_linkage_name, gimli::DW_AT_name]
+        .iter()
+        .find_map(|&attribute| attribute_value(unit, offset, attribute))
+        .and_then(|value| dwarf.attr_string(unit, value).ok())
+        .map(|name| name.to_string_lossy().into_owned())
+}
+
+fn declaration(
+    dwarf: &Dwarf,
+    unit: &gimli::Unit<Slice>,
+    offset: UnitOffset,
+) -> Option<SourceLocation> {
+    let file = match attribute_value(unit, offset, gimli::DW_AT_decl_file)? {
+        AttributeValue::FileIndex(index) => index,
+        value => value.udata_value()?,
+    };
+    let line = attribute_value(unit, offset, gimli::DW_AT_decl_line)?.udata_value()?;
+    let header = unit.line_program.as_ref()?.header();
+    let comp_dir = unit
+        .comp_dir
+        .map(|dir| PathBuf::from(dir.to_string_lossy().into_owned()));
+    Some(SourceLocation {
+        file: file_path(dwarf, unit, header, header.file(file)?, comp_dir.as_ref())?,
+        line,
+        column: 0,
+    })
+}
+
+/// Returns the value of an attribute of the entry at the given offset, looking it up in the entry
+/// from which that one was derived if it has none, as inlined and out-of-line instances of a
+/// function only refer to the entry which names and declares it.
+fn attribute_value<'a>(
+    unit: &gimli::Unit<Slice<'a>>,
+    mut offset: UnitOffset,
+    attribute: DwAt,
+) -> Option<AttributeValue<Slice<'a>>> {
+    for _ in 0..MAX_ORIGIN_DEPTH {
+        let entry = unit.entry(offset).ok()?;
+        if let Ok(Some(value)) = entry.attr_value(attribute) {
+            return Some(value);
+        }
+        offset = [gimli::DW_AT_abstract_origin, gimli::DW_AT_specification]
+            .iter()
+            .find_map(|&origin| match entry.attr_value(origin) {
+                Ok(Some(AttributeValue::UnitRef(offset))) => Some(offset),
+                _ => None,
+            })?;
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::debug_info::functions::{subprograms, InlinedCode};
+    use parity_wasm::deserialize_buffer;
+    use parity_wasm::elements::{Instruction, Module};
+
+    #[test]
+    fn reads_declarations_of_functions() {
+        let contents = include_bytes!("../runner/res/cases/test-example.wasm");
+        let module = deserialize_buffer::<Module>(contents).unwrap();
+
+        let to_roman = subprograms(&module, contents)
+            .into_iter()
+            .find(|subprogram| {
+                subprogram
+                    .name
+                    .as_ref()
+                    .is_some_and(|name| name.contains("roman_numerals8to_roman"))
+            })
+            .unwrap();
+
+        let declaration = to_roman.declaration.unwrap();
+        assert!(declaration.file.ends_with("src/lib.rs"));
+        assert_eq!(declaration.line, 17);
+    }
+
+    #[test]
+    fn locates_instructions_compiled_from_inlined_functions() {
+        let contents = include_bytes!("../runner/res/cases/test-example.wasm");
+        let module = deserialize_buffer::<Module>(contents).unwrap();
+        let inlined_code = InlinedCode::new(&module, contents).unwrap();
+        let bodies = module.code_section().unwrap().bodies();
+
+        let size_of = (0..bodies.len())
+            .flat_map(|body_index| {
+                let instructions = bodies[body_index].code().elements().len();
+                (0..instructions).map(move |instruction| (body_index, instruction))
+            })
+            .filter_map(|(body_index, instruction)| {
+                inlined_code.function_at(body_index, instruction)
+            })
+            .find(|function| function.name.contains("4core3mem7size_of"));
+
+        assert!(size_of.is_some());
+    }
+
+    #[test]
+    fn has_no_inlined_code_without_debug_information() {
+        let contents = crate::runner::tests::test_module(vec![Instruction::End]);
+        let module = deserialize_buffer::<Module>(&contents).unwrap();
+
+        assert!(InlinedCode::new(&module, &contents).is_none());
+    }
+}
@@ -0,0 +1,239 @@
+use crate::debug_info::{instruction_offsets, load_dwarf, Dwarf, Slice};
+use parity_wasm::elements::Module;
+use std::fmt::{self, Display, Formatter};
+use std::path::PathBuf;
+
+/// Position in the source code from which an instruction was compiled.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct SourceLocation {
+    pub file: PathBuf,
+    /// Line number, starting at 1.
+    pub line: u64,
+    /// Column number, starting at 1, or 0 if the compiler did not record it.
+    pub column: u64,
+}
+
+impl Display for SourceLocation {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}", self.file.display(), self.line)?;
+        if self.column > 0 {
+            write!(f, ":{}", self.column)?;
+        }
+        Ok(())
+    }
+}
+
+/// A row of the line table: code from `address` up to the next row was compiled from `location`.
+struct Row {
+    address: u64,
+    location: Option<SourceLocation>,
+}
+
+/// Maps instructions to the source code they were compiled from, using the `.debug_line` section.
+pub(crate) struct SourceLocator {
+    /// Sequences of rows of contiguous code, each ordered by address and ending with the address
+    /// just past the sequence. The sequences are ordered by their first address.
+    sequences: Vec<Vec<Row>>,
+    instruction_offsets: Vec<Vec<usize>>,
+}
+
+impl SourceLocator {
+    /// Reads the line table of the module, which must have been parsed from the given contents.
+    /// Returns `None` if the module has no line table.
+    pub(crate) fn new(module: &Module, module_contents: &[u8]) -> Option<Self> {
+        let dwarf = load_dwarf(module)?;
+        let sequences = read_sequences(&dwarf);
+        if sequences.is_empty() {
+            return None;
+        }
+        Some(SourceLocator::with_sequences(
+            sequences,
+            instruction_offsets(module_contents)?,
+        ))
+    }
+
+    fn with_sequences(mut sequences: Vec<Vec<Row>>, instruction_offsets: Vec<Vec<usize>>) -> Self {
+        sequences.sort_by_key(|rows| rows[0].address);
+        SourceLocator {
+            sequences,
+            instruction_offsets,
+        }
+    }
+
+    /// Returns the source location of the given instruction of the given function body.
+    pub(crate) fn locate(
+        &self,
+        body_index: usize,
+        instruction_index: usize,
+    ) -> Option<SourceLocation> {
+        let offset = *self
+            .instruction_offsets
+            .get(body_index)?
+            .get(instruction_index)? as u64;
+        self.locate_offset(offset)
+    }
+
+    /// Returns the source locations of all instructions of the given function body.
+    pub(crate) fn locate_body(&self, body_index: usize) -> Vec<SourceLocation> {
+        self.instruction_offsets
+            .get(body_index)
+            .map_or(&[][..], Vec::as_slice)
+            .iter()
+            .filter_map(|&offset| self.locate_offset(offset as u64))
+            .collect()
+    }
+
+    fn locate_offset(&self, offset: u64) -> Option<SourceLocation> {
+        let following = self
+            .sequences
+            .partition_point(|rows| rows[0].address <= offset);
+        let rows = &self.sequences[following.checked_sub(1)?];
+        if offset >= rows.last()?.address {
+            return None;
+        }
+        let row = &rows[rows.partition_point(|row| row.address <= offset) - 1];
+        row.location.clone()
+    }
+}
+
+fn read_sequences(dwarf: &Dwarf) -> Vec<Vec<Row>> {
+    let mut sequences = Vec::new();
+    let mut units = dwarf.units();
+    while let Ok(Some(header)) = units.next() {
+        let unit = match dwarf.unit(header) {
+            Ok(unit) => unit,
+            Err(_) => continue,
+        };
+        let program = match unit.line_program.clone() {
+            Some(program) => program,
+            None => continue,
+        };
+        let comp_dir = unit
+            .comp_dir
+            .map(|dir| PathBuf::from(dir.to_string_lossy().into_owned()));
+        let mut rows = program.rows();
+        let mut sequence = Vec::new();
+        while let Ok(Some((header, row))) = rows.next_row() {
+            if row.end_sequence() {
+                sequence.push(Row {
+                    address: row.address(),
+                    location: None,
+                });
+                // Sequences of functions discarded by the linker start at address 0.
+                if sequence.len() > 1 && sequence[0].address > 0 {
+                    sequences.push(sequence);
+                }
+                sequence = Vec::new();
+                continue;
+            }
+            let file = row
+                .file(header)
+                .and_then(|file| file_path(dwarf, &unit, header, file, comp_dir.as_ref()));
+            let location = match (file, row.line()) {
+                (Some(file), Some(line)) => Some(SourceLocation {
+                    file,
+                    line: line.get(),
+                    column: match row.column() {
+                        gimli::ColumnType::LeftEdge => 0,
+                        gimli::ColumnType::Column(column) => column.get(),
+                    },
+                }),
+                _ => None,
+            };
+            sequence.push(Row {
+                address: row.address(),
+                location,
+            });
+        }
+    }
+    sequences
+}
+
+pub(super) fn file_path(
+    dwarf: &Dwarf,
+    unit: &gimli::Unit<Slice>,
+    header: &gimli::LineProgramHeader<Slice>,
+    file: &gimli::FileEntry<Slice>,
+    comp_dir: Option<&PathBuf>,
+) -> Option<PathBuf> {
+    let mut path = comp_dir.cloned().unwrap_or_default();
+    if let Some(directory) = file.directory(header) {
+        let directory = dwarf.attr_string(unit, directory).ok()?;
+        path.push(directory.to_string_lossy().as_ref());
+    }
+    let name = dwarf.attr_string(unit, file.path_name()).ok()?;
+    path.push(name.to_string_lossy().as_ref());
+    Some(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::debug_info::lines::Row;
+    use crate::debug_info::{SourceLocation, SourceLocator};
+    use parity_wasm::deserialize_buffer;
+    use parity_wasm::elements::{Instruction, Module};
+    use std::path::PathBuf;
+
+    fn row(address: u64, line: Option<u64>) -> Row {
+        Row {
+            address,
+            location: line.map(|line| SourceLocation {
+                file: PathBuf::from("src/lib.rs"),
+                line,
+                column: 0,
+            }),
+        }
+    }
+
+    #[test]
+    fn locates_instructions_in_source_files() {
+        let contents = include_bytes!("../runner/res/cases/test-example.wasm");
+        let module = deserialize_buffer::<Module>(contents)
+            .unwrap()
+            .parse_names()
+            .unwrap();
+        let locator = SourceLocator::new(&module, contents).unwrap();
+        let to_roman = module
+            .names_section()
+            .unwrap()
+            .functions()
+            .unwrap()
+            .names()
+            .iter()
+            .find(|(_, name)| name.contains("roman_numerals8to_roman"))
+            .map(|(index, _)| index as usize)
+            .unwrap();
+
+        let location = locator.locate(to_roman, 1).unwrap();
+
+        assert!(location.file.ends_with("src/lib.rs"));
+        assert!(location.line > 0);
+    }
+
+    #[test]
+    fn finds_sequence_containing_offset() {
+        let locator = SourceLocator::with_sequences(
+            vec![
+                vec![row(40, Some(7)), row(44, Some(8)), row(50, None)],
+                vec![row(10, Some(1)), row(20, Some(2)), row(30, None)],
+            ],
+            Vec::new(),
+        );
+
+        let line = |offset| locator.locate_offset(offset).map(|location| location.line);
+        assert_eq!(line(5), None);
+        assert_eq!(line(10), Some(1));
+        assert_eq!(line(29), Some(2));
+        assert_eq!(line(35), None);
+        assert_eq!(line(45), Some(8));
+        assert_eq!(line(50), None);
+    }
+
+    #[test]
+    fn has_no_locations_without_debug_information() {
+        let contents = crate::runner::tests::test_module(vec![Instruction::End]);
+        let module = deserialize_buffer::<Module>(&contents).unwrap();
+
+        assert!(SourceLocator::new(&module, &contents).is_none());
+    }
+}
@@ -0,0 +1,79 @@
+//! Reading of the DWARF debugging information which compilers embed in WebAssembly modules as
+//! custom sections. DWARF addresses code by its offset from the start of the contents of the code
+//! section.
+
+mod functions;
+mod lines;
+mod sources;
+
+pub(crate) use functions::{subprograms, InlinedCode};
+pub use lines::SourceLocation;
+pub(crate) use lines::SourceLocator;
+pub(crate) use sources::SourceFiles;
+
+use gimli::{EndianSlice, LittleEndian, SectionId};
+use parity_wasm::elements::Module;
+use std::ops::Range;
+use wasmparser::{Parser, Payload};
+
+pub(crate) type Dwarf<'a> = gimli::Dwarf<Slice<'a>>;
+type Slice<'a> = EndianSlice<'a, LittleEndian>;
+
+/// Loads the DWARF sections of the module. Missing sections are treated as empty.
+pub(crate) fn load_dwarf(module: &Module) -> Option<Dwarf<'_>> {
+    gimli::Dwarf::load(|id: SectionId| {
+        let data = module
+            .custom_sections()
+            .find(|section| section.name() == id.name())
+            .map_or(&[][..], |section| section.payload());
+        Ok::<_, gimli::Error>(EndianSlice::new(data, LittleEndian))
+    })
+    .ok()
+}
+
+/// Returns the range of each function body, including its size, relative to the start of the
+/// contents of the code section.
+pub(crate) fn code_body_ranges(module_contents: &[u8]) -> Option<Vec<Range<usize>>> {
+    let mut ranges = Vec::new();
+    let mut section_start = 0;
+    let mut previous_end = 0;
+    for payload in Parser::new(0).parse_all(module_contents) {
+        match payload.ok()? {
+            Payload::CodeSectionStart { range, .. } => {
+                section_start = range.start;
+                previous_end = range.start;
+            }
+            Payload::CodeSectionEntry(body) => {
+                let end = body.range().end;
+                ranges.push(previous_end - section_start..end - section_start);
+                previous_end = end;
+            }
+            _ => {}
+        }
+    }
+    Some(ranges)
+}
+
+/// Returns, for each function body, the offset of each of its instructions relative to the start
+/// of the contents of the code section. Instructions are numbered as in the bodies parsed by
+/// `parity_wasm`.
+pub(crate) fn instruction_offsets(module_contents: &[u8]) -> Option<Vec<Vec<usize>>> {
+    let mut offsets = Vec::new();
+    let mut section_start = 0;
+    for payload in Parser::new(0).parse_all(module_contents) {
+        match payload.ok()? {
+            Payload::CodeSectionStart { range, .. } => section_start = range.start,
+            Payload::CodeSectionEntry(body) => {
+                let mut reader = body.get_operators_reader().ok()?;
+                let mut body_offsets = Vec::new();
+                while !reader.eof() {
+                    let (_, offset) = reader.read_with_offset().ok()?;
+                    body_offsets.push(offset - section_start);
+                }
+                offsets.push(body_offsets);
+            }
+            _ => {}
+        }
+    }
+    Some(offsets)
+}
@@ -0,0 +1,31 @@
+use crate::SourceLocation;
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Local source files named by the debugging information, each read once when first needed.
+#[derive(Default)]
+pub(crate) struct SourceFiles {
+    /// Lines of each file, or `None` if it could not be read.
+    files: HashMap<PathBuf, Option<Vec<String>>>,
+}
+
+impl SourceFiles {
+    /// Returns the lines of the given file, or `None` if it cannot be read.
+    pub(crate) fn lines(&mut self, file: &Path) -> Option<&[String]> {
+        self.files
+            .entry(file.to_path_buf())
+            .or_insert_with(|| {
+                fs::read_to_string(file)
+                    .ok()
+                    .map(|source| source.lines().map(String::from).collect())
+            })
+            .as_deref()
+    }
+
+    /// Returns the text of the line at the given location.
+    pub(crate) fn line(&mut self, location: &SourceLocation) -> Option<&str> {
+        let index = (location.line as usize).checked_sub(1)?;
+        self.lines(&location.file)?.get(index).map(String::as_str)
+    }
+}
@@ -0,0 +1,216 @@
+//! Selection of the source lines which a change added or modified, so that only the code touched
+//! by a pull request is mutated.
+
+use crate::{Error, SourceLocation};
+use std::collections::{BTreeSet, HashMap};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// Lines added or modified by a change, by file. Files are relative to the root of the
+/// repository, as in a diff.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ChangedLines {
+    files: HashMap<PathBuf, BTreeSet<u64>>,
+}
+
+impl ChangedLines {
+    /// Reads the lines added or modified in the new version of each file of a unified diff, as
+    /// written by `git diff` or `diff -u`. Removed lines are ignored, since there is no code left
+    /// to mutate for them.
+    pub fn from_unified_diff(diff: &str) -> Result<Self, Error> {
+        let mut changed = ChangedLines::default();
+        let mut file: Option<PathBuf> = None;
+        let mut line = 0;
+        // Lines of the old and new versions of the file which the current hunk has yet to list.
+        // File and hunk headers are only read between hunks, since a line of a hunk may look
+        // like one.
+        let mut old_remaining: u64 = 0;
+        let mut new_remaining: u64 = 0;
+        for text in diff.lines() {
+            if old_remaining > 0 || new_remaining > 0 {
+                let too_long = || Error::Diff(String::from("hunk longer than its header counts"));
+                if text.starts_with('+') {
+                    if let Some(file) = &file {
+                        changed.files.entry(file.clone()).or_default().insert(line);
+                    }
+                    line += 1;
+                    new_remaining = new_remaining.checked_sub(1).ok_or_else(too_long)?;
+                } else if text.starts_with('-') {
+                    old_remaining = old_remaining.checked_sub(1).ok_or_else(too_long)?;
+                } else if text.starts_with(' ') || text.is_empty() {
+                    line += 1;
+                    old_remaining = old_remaining.checked_sub(1).ok_or_else(too_long)?;
+                    new_remaining = new_remaining.checked_sub(1).ok_or_else(too_long)?;
+                }
+            } else if let Some(path) = text.strip_prefix("+++ ") {
+                let path = path.split('\t').next().unwrap_or(path).trim_end();
+                file = if path == "/dev/null" {
+                    None
+                } else {
+                    Some(PathBuf::from(path.strip_prefix("b/").unwrap_or(path)))
+                };
+            } else if let Some(hunk) = text.strip_prefix("@@ ") {
+                let hunk = parse_hunk_header(hunk)
+                    .ok_or_else(|| Error::Diff(format!("invalid hunk header `{}`", text)))?;
+                line = hunk.new_start;
+                old_remaining = hunk.old_count;
+                new_remaining = hunk.new_count;
+            }
+        }
+        Ok(changed)
+    }
+
+    /// Reads the lines added or modified by the unified diff in the given file.
+    pub fn read(path: &Path) -> Result<Self, Error> {
+        let diff = fs::read_to_string(path)
+            .map_err(|e| Error::Diff(format!("could not read {}: {}", path.display(), e)))?;
+        Self::from_unified_diff(&diff).map_err(|e| match e {
+            Error::Diff(message) => Error::Diff(format!("{}: {}", path.display(), message)),
+            e => e,
+        })
+    }
+
+    /// Runs `git diff` in the given directory to find the lines changed since the given revision,
+    /// including uncommitted changes.
+    pub fn from_git(directory: &Path, base: &str) -> Result<Self, Error> {
+        let output = Command::new("git")
+            .current_dir(directory)
+            .args(["diff", "--no-color", "--no-ext-diff", "--unified=0", base])
+            .output()
+            .map_err(|e| Error::Diff(format!("could not run git: {}", e)))?;
+        if !output.status.success() {
+            return Err(Error::Diff(format!(
+                "git diff {} failed: {}",
+                base,
+                String::from_utf8_lossy(&output.stderr).trim()
+            )));
+        }
+        Self::from_unified_diff(&String::from_utf8_lossy(&output.stdout))
+    }
+
+    /// Whether the change touched the given location. Debugging information usually records
+    /// absolute paths, so a file of the diff matches any path which ends with it.
+    pub fn contains(&self, location: &SourceLocation) -> bool {
+        self.files
+            .iter()
+            .any(|(file, lines)| location.file.ends_with(file) && lines.contains(&location.line))
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.files.is_empty()
+    }
+}
+
+/// Ranges of the old and new versions of the file listed by a hunk.
+struct HunkHeader {
+    old_count: u64,
+    new_start: u64,
+    new_count: u64,
+}
+
+/// Parses a hunk header such as `-12,3 +14,5 @@ fn context()`. A range without a count, such as
+/// `-30`, spans one line.
+fn parse_hunk_header(hunk: &str) -> Option<HunkHeader> {
+    let mut ranges = hunk.split_whitespace();
+    let (_, old_count) = parse_range(ranges.next()?.strip_prefix('-')?)?;
+    let (new_start, new_count) = parse_range(ranges.next()?.strip_prefix('+')?)?;
+    Some(HunkHeader {
+        old_count,
+        new_start,
+        new_count,
+    })
+}
+
+fn parse_range(range: &str) -> Option<(u64, u64)> {
+    match range.split_once(',') {
+        Some((start, count)) => Some((start.parse().ok()?, count.parse().ok()?)),
+        None => Some((range.parse().ok()?, 1)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::diff::ChangedLines;
+    use crate::{Error, SourceLocation};
+    use std::path::PathBuf;
+
+    const DIFF: &str = "\
+diff --git a/src/lib.rs b/src/lib.rs
+index 0123456..789abcd 100644
+--- a/src/lib.rs
++++ b/src/lib.rs
+@@ -10,4 +10,5 @@ fn to_roman(number: u32) -> String {
+     let mut result = String::new();
+-    if number > 10 {
++    if number >= 10 {
++        result.push('X');
+     }
+     result
+@@ -30 +31,0 @@ mod tests {
+-    // Obsolete
+";
+
+    fn location(file: &str, line: u64) -> SourceLocation {
+        SourceLocation {
+            file: PathBuf::from(file),
+            line,
+            column: 1,
+        }
+    }
+
+    #[test]
+    fn records_added_and_modified_lines_of_new_file() {
+        let changed = ChangedLines::from_unified_diff(DIFF).unwrap();
+
+        assert!(!changed.contains(&location("/home/user/numerals/src/lib.rs", 10)));
+        assert!(changed.contains(&location("/home/user/numerals/src/lib.rs", 11)));
+        assert!(changed.contains(&location("/home/user/numerals/src/lib.rs", 12)));
+        assert!(!changed.contains(&location("/home/user/numerals/src/lib.rs", 13)));
+        assert!(!changed.contains(&location("/home/user/numerals/src/lib.rs", 31)));
+    }
+
+    #[test]
+    fn matches_files_by_whole_path_components() {
+        let changed = ChangedLines::from_unified_diff(DIFF).unwrap();
+
+        assert!(!changed.contains(&location("/home/user/numerals/src/mylib.rs", 11)));
+        assert!(!changed.contains(&location("/home/user/numerals/tests/lib.rs", 11)));
+    }
+
+    #[test]
+    fn reads_added_lines_which_look_like_headers_as_part_of_the_hunk() {
+        let changed = ChangedLines::from_unified_diff(
+            "--- a/src/lib.rs\n+++ b/src/lib.rs\n@@ -1,1 +1,3 @@\n fn main() {}\n+++ x\n+--- y\n",
+        )
+        .unwrap();
+
+        assert!(changed.contains(&location("/numerals/src/lib.rs", 2)));
+        assert!(changed.contains(&location("/numerals/src/lib.rs", 3)));
+        assert!(!changed.contains(&location("/numerals/x", 3)));
+    }
+
+    #[test]
+    fn ignores_deleted_files() {
+        let changed = ChangedLines::from_unified_diff(
+            "--- a/src/old.rs\n+++ /dev/null\n@@ -1,2 +0,0 @@\n-fn old() {}\n-\n",
+        )
+        .unwrap();
+
+        assert!(changed.is_empty());
+    }
+
+    #[test]
+    fn fails_on_hunk_longer_than_its_header_counts() {
+        let result = ChangedLines::from_unified_diff("+++ b/a.rs\n@@ -1,0 +1,1 @@\n-x\n-y\n");
+
+        assert!(matches!(result, Err(Error::Diff(_))));
+    }
+
+    #[test]
+    fn fails_on_invalid_hunk_header() {
+        let result = ChangedLines::from_unified_diff("+++ b/src/lib.rs\n@@ nonsense @@\n");
+
+        assert!(matches!(result, Err(Error::Diff(_))));
+    }
+}
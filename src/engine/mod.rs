@@ -1,73 +1,866 @@
+mod results;
+
+use crate::candidates::{FunctionFilter, FunctionNames, SymbolPath};
+use crate::debug_info::{InlinedCode, SourceLocator};
 use crate::mutation::Mutation;
-use crate::runner::TestResult;
-use crate::{candidates, mutation, runner};
+use crate::runner::{RunnerOptions, TestResult};
+use crate::skip::{self, SkipAnnotations};
+use crate::{candidates, mutation, runner, AcceptedMutants, ChangedLines, Error};
 use parity_wasm::deserialize_buffer;
 use parity_wasm::elements::Module;
+use rayon::prelude::*;
+use rayon::ThreadPoolBuilder;
+pub use results::{Baseline, MutantOutcome, MutantResult, MutationTestResults, Totals};
+use std::collections::HashSet;
+use std::thread;
+use std::time::Instant;
+
+/// Default ratio of the fuel which a mutant may use to that used by the original tests.
+pub const DEFAULT_TIMEOUT_FACTOR: u64 = 10;
+
+/// Fuel which a mutant may use at least, however little the original tests use.
+const MINIMUM_FUEL: u64 = 100_000;
+
+/// Configuration of a mutation testing run over a single WebAssembly module.
+///
+/// ```no_run
+/// use mutation_test_engine::MutationTestRun;
+///
+/// let contents = std::fs::read("roman_numerals.wasm").unwrap();
+/// let results = MutationTestRun::new(&contents)
+///     .include_module("roman_numerals")
+///     .exclude_module("roman_numerals::tests")
+///     .run()
+///     .unwrap();
+/// println!("Mutation score: {:?}", results.mutation_score());
+/// ```
+#[derive(Clone, Debug)]
+pub struct MutationTestRun<'a> {
+    module_contents: &'a [u8],
+    include_modules: Vec<String>,
+    exclude_modules: Vec<String>,
+    default_excludes: bool,
+    changed_lines: Option<ChangedLines>,
+    skip_annotations: bool,
+    mutators: Vec<String>,
+    disabled_mutators: Vec<String>,
+    mutant_ids: Vec<String>,
+    accepted_mutants: AcceptedMutants,
+    runner_options: RunnerOptions,
+    timeout_factor: u64,
+    jobs: usize,
+}
+
+impl<'a> MutationTestRun<'a> {
+    pub fn new(module_contents: &'a [u8]) -> Self {
+        MutationTestRun {
+            module_contents,
+            include_modules: Vec::new(),
+            exclude_modules: Vec::new(),
+            default_excludes: true,
+            changed_lines: None,
+            skip_annotations: true,
+            mutators: Vec::new(),
+            disabled_mutators: Vec::new(),
+            mutant_ids: Vec::new(),
+            accepted_mutants: AcceptedMutants::default(),
+            runner_options: RunnerOptions::default(),
+            timeout_factor: DEFAULT_TIMEOUT_FACTOR,
+            jobs: default_jobs(),
+        }
+    }
+
+    /// Mutates functions whose demangled name matches the given pattern, which is either a glob
+    /// over `::`-separated path segments or, prefixed with `re:`, a regular expression (see
+    /// [`crate::candidates::Pattern`]).
+    pub fn include_module(mut self, pattern: impl Into<String>) -> Self {
+        self.include_modules.push(pattern.into());
+        self
+    }
+
+    pub fn include_modules<I, S>(mut self, patterns: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.include_modules
+            .extend(patterns.into_iter().map(Into::into));
+        self
+    }
+
+    /// Never mutates functions whose demangled name matches the given pattern, even if they are
+    /// included.
+    pub fn exclude_module(mut self, pattern: impl Into<String>) -> Self {
+        self.exclude_modules.push(pattern.into());
+        self
+    }
+
+    pub fn exclude_modules<I, S>(mut self, patterns: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.exclude_modules
+            .extend(patterns.into_iter().map(Into::into));
+        self
+    }
+
+    /// Sets whether the functions matched by [`candidates::DEFAULT_EXCLUDE_PATTERNS`], i.e. drop
+    /// glue and standard library code, are excluded. They are by default.
+    pub fn default_excludes(mut self, enabled: bool) -> Self {
+        self.default_excludes = enabled;
+        self
+    }
+
+    /// Only mutates instructions compiled from the given lines, e.g. those changed by a pull
+    /// request. This requires the module to contain a line table.
+    pub fn changed_lines(mut self, changed_lines: ChangedLines) -> Self {
+        self.changed_lines = Some(changed_lines);
+        self
+    }
+
+    /// Sets whether functions and lines annotated with `// mutants: skip` or `#[mutants::skip]` in
+    /// their source files, or listed in a `mutants.skip` custom section, are left unmutated. They
+    /// are by default.
+    pub fn skip_annotations(mut self, enabled: bool) -> Self {
+        self.skip_annotations = enabled;
+        self
+    }
+
+    /// Applies only the mutators with the given IDs or kinds (see
+    /// [`crate::mutation::mutator_names`]). The mutators enabled by default are applied if none
+    /// is enabled.
+    pub fn enable_mutator(mut self, name: impl Into<String>) -> Self {
+        self.mutators.push(name.into());
+        self
+    }
+
+    pub fn enable_mutators<I, S>(mut self, names: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.mutators.extend(names.into_iter().map(Into::into));
+        self
+    }
+
+    /// Does not apply the mutators with the given ID or kind, even if they are enabled.
+    pub fn disable_mutator(mut self, name: impl Into<String>) -> Self {
+        self.disabled_mutators.push(name.into());
+        self
+    }
+
+    pub fn disable_mutators<I, S>(mut self, names: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.disabled_mutators
+            .extend(names.into_iter().map(Into::into));
+        self
+    }
+
+    /// Runs only the mutant with the given ID (see [`Mutation::id`]), e.g. to check whether a
+    /// surviving mutant has been killed. All mutants are run if none is selected. The run fails
+    /// with [`Error::UnknownMutants`] if no mutant of the selected functions and lines has the ID.
+    pub fn select_mutant(mut self, id: impl Into<String>) -> Self {
+        self.mutant_ids.push(id.into());
+        self
+    }
+
+    pub fn select_mutants<I, S>(mut self, ids: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.mutant_ids.extend(ids.into_iter().map(Into::into));
+        self
+    }
+
+    /// Reports surviving mutants with the IDs of the given accepted mutants as
+    /// [`MutantOutcome::Accepted`] rather than as survivors.
+    pub fn accepted_mutants(mut self, accepted_mutants: AcceptedMutants) -> Self {
+        self.accepted_mutants = accepted_mutants;
+        self
+    }
+
+    /// Sets how the tests are run. If the options do not limit the fuel of the tests, each mutant
+    /// is given fuel in proportion to that used by the original tests.
+    pub fn runner_options(mut self, runner_options: RunnerOptions) -> Self {
+        self.runner_options = runner_options;
+        self
+    }
+
+    /// Reports mutants as timed out once they execute this many times the instructions executed
+    /// by the original tests.
+    pub fn timeout_factor(mut self, timeout_factor: u64) -> Self {
+        self.timeout_factor = timeout_factor;
+        self
+    }
+
+    /// Runs this many mutants at once. Defaults to the number of available CPU cores. The order
+    /// of the results does not depend on it.
+    pub fn jobs(mut self, jobs: usize) -> Self {
+        self.jobs = jobs.max(1);
+        self
+    }
+
+    /// Runs the original tests, failing if they do not pass, and then runs them against each
+    /// mutant.
+    pub fn run(&self) -> Result<MutationTestResults, Error> {
+        let module = load_module(self.module_contents)?;
+        let include_modules: Vec<&str> = self.include_modules.iter().map(String::as_str).collect();
+        let mut exclude_modules: Vec<&str> =
+            self.exclude_modules.iter().map(String::as_str).collect();
+        if self.default_excludes {
+            exclude_modules.extend(candidates::DEFAULT_EXCLUDE_PATTERNS.iter());
+        }
+        let skipped_functions = if self.skip_annotations {
+            skip::section_patterns(&module)
+        } else {
+            Vec::new()
+        };
+        exclude_modules.extend(skipped_functions.iter().map(String::as_str));
+        let function_names = FunctionNames::read(&module, self.module_contents)?;
+        let filter = FunctionFilter::new(&include_modules, &exclude_modules)?;
+        let mut candidates = candidates::select_functions(&module, &function_names, &filter);
+        let inlined_code = InlinedCode::new(&module, self.module_contents);
+        let mut inlining_functions = HashSet::new();
+        if let Some(inlined_code) = &inlined_code {
+            let inlining = candidates::identify_functions_with_inlined_candidates(
+                &module,
+                &function_names,
+                &filter,
+                inlined_code,
+            );
+            inlining_functions.extend(inlining.iter().map(|candidate| candidate.body_index));
+            candidates.extend(inlining);
+            candidates.sort_by_key(|candidate| candidate.function_index);
+        }
+        let mut annotations = if self.skip_annotations {
+            Some(SkipAnnotations::new(&module, self.module_contents))
+        } else {
+            None
+        };
+        if let Some(annotations) = &mut annotations {
+            annotations.remove_skipped_functions(&mut candidates);
+        }
+        let locator = SourceLocator::new(&module, self.module_contents);
+        if let Some(changed_lines) = &self.changed_lines {
+            let locator = locator.as_ref().ok_or(Error::MissingDebugInfo)?;
+            candidates.retain(|candidate| {
+                locator
+                    .locate_body(candidate.body_index)
+                    .iter()
+                    .any(|location| changed_lines.contains(location))
+            });
+        }
+        let body_indices: Vec<usize> = candidates.iter().map(|c| c.body_index).collect();
+        let code_section = module.code_section().ok_or(Error::MissingCodeSection)?;
+        let bodies = code_section.bodies();
+        let mutators = mutation::enabled_mutators(&self.mutators, &self.disabled_mutators);
+        let start = Instant::now();
+        let baseline =
+            runner::run_tests_with_coverage(&module, &body_indices, &self.runner_options)?;
+        let duration = start.elapsed();
+        if baseline.result != TestResult::Passed {
+            return Err(Error::BaselineFailed(baseline.result));
+        }
+        let mutant_options = RunnerOptions {
+            fuel: Some(self.runner_options.fuel.unwrap_or_else(|| {
+                (baseline.fuel_consumed.saturating_mul(self.timeout_factor)).max(MINIMUM_FUEL)
+            })),
+            ..self.runner_options.clone()
+        };
+        let mut mutations = mutation::find_mutations(&candidates, bodies, &mutators)?;
+        if let Some(locator) = &locator {
+            for mutation in &mut mutations {
+                mutation.location = locator.locate(mutation.body_index, mutation.instruction_index);
+            }
+        }
+        if let Some(inlined_code) = &inlined_code {
+            mutations.retain_mut(|mutation| {
+                attribute_to_inlined_function(
+                    mutation,
+                    inlined_code,
+                    &filter,
+                    &inlining_functions,
+                    annotations.as_mut(),
+                )
+            });
+        }
+        mutation::assign_ids(&mut mutations, bodies);
+        let mut mutator_kinds: Vec<&str> = mutators.iter().map(|mutator| mutator.name()).collect();
+        mutator_kinds.dedup();
+        let missing_accepted = self
+            .accepted_mutants
+            .missing(mutations.iter().map(Mutation::id), &mutator_kinds);
+        if let Some(changed_lines) = &self.changed_lines {
+            mutations.retain(|mutation| {
+                mutation
+                    .location()
+                    .is_some_and(|location| changed_lines.contains(location))
+            });
+        }
+        if let Some(annotations) = &mut annotations {
+            mutations.retain(|mutation| {
+                !mutation
+                    .location()
+                    .is_some_and(|location| annotations.skips_line(location))
+            });
+        }
+        // Selected mutants are looked up among those left by the other filters, so that a
+        // mutant which they remove is reported rather than silently not run.
+        if !self.mutant_ids.is_empty() {
+            let unknown: Vec<String> = self
+                .mutant_ids
+                .iter()
+                .filter(|id| !mutations.iter().any(|mutation| mutation.id() == *id))
+                .cloned()
+                .collect();
+            if !unknown.is_empty() {
+                return Err(Error::UnknownMutants(unknown));
+            }
+            mutations.retain(|mutation| self.mutant_ids.iter().any(|id| id == mutation.id()));
+        }
+        let mut mutants = run_mutations(
+            self.module_contents,
+            mutations,
+            &baseline.covered_functions,
+            &mutant_options,
+            self.jobs,
+        );
+        for result in &mut mutants {
+            if result.outcome == MutantOutcome::Survived
+                && self.accepted_mutants.contains(result.mutation.id())
+            {
+                result.outcome = MutantOutcome::Accepted;
+            }
+        }
+        Ok(MutationTestResults {
+            naming_source: function_names.source,
+            baseline: Baseline {
+                duration,
+                fuel_consumed: baseline.fuel_consumed,
+            },
+            mutants,
+            missing_accepted,
+        })
+    }
+}
 
 pub fn find_surviving_mutants(
     original_module_contents: &[u8],
     include_modules: &[&str],
     exclude_modules: &[&str],
-) -> Vec<Mutation> {
-    let module = load_module(original_module_contents);
-    let indices =
-        candidates::identify_candidate_functions(&module, include_modules, exclude_modules);
-    let code_section = module.code_section().unwrap();
-    let bodies = code_section.bodies();
-    let mutations = mutation::find_mutations(indices, bodies);
-    run_mutations(original_module_contents, mutations)
+) -> Result<Vec<Mutation>, Error> {
+    Ok(MutationTestRun::new(original_module_contents)
+        .include_modules(include_modules.iter().copied())
+        .exclude_modules(exclude_modules.iter().copied())
+        .run()?
+        .mutants
+        .into_iter()
+        .filter(|result| result.outcome == MutantOutcome::Survived)
+        .map(|result| result.mutation)
+        .collect())
 }
 
-fn run_mutations(original_module_contents: &[u8], mutations: Vec<Mutation>) -> Vec<Mutation> {
-    let mut surviving_mutants = Vec::new();
-    for mutation in mutations {
-        let mut mutated_module = load_module(original_module_contents);
-        mutation.perform(&mut mutated_module);
-        let serialized = mutated_module.to_bytes().unwrap();
-        if runner::run_tests(serialized.as_slice()) == TestResult::Passed {
-            surviving_mutants.push(mutation);
-        }
+/// Attributes the mutation to the innermost inlined function from which the mutated instruction
+/// was compiled, if any. Returns whether that function, or else the mutated one, is to be mutated:
+/// functions with inlined code are only mutated for the sake of the selected inlined functions.
+fn attribute_to_inlined_function(
+    mutation: &mut Mutation,
+    inlined_code: &InlinedCode,
+    filter: &FunctionFilter,
+    inlining_functions: &HashSet<usize>,
+    annotations: Option<&mut SkipAnnotations>,
+) -> bool {
+    let inlined = match inlined_code.function_at(mutation.body_index, mutation.instruction_index) {
+        Some(inlined) => inlined,
+        None => return !inlining_functions.contains(&mutation.body_index),
+    };
+    let path = SymbolPath::from_symbol(&inlined.name);
+    let skipped = match (annotations, &inlined.declaration) {
+        (Some(annotations), Some(declaration)) => annotations.skips_function(declaration),
+        _ => false,
+    };
+    if skipped || !filter.selects(&path) {
+        return false;
     }
-    surviving_mutants
+    mutation.attribute_to_inlined_function(path.to_string());
+    true
 }
 
-fn load_module(contents: &[u8]) -> Module {
+fn run_mutations(
+    original_module_contents: &[u8],
+    mutations: Vec<Mutation>,
+    covered_functions: &HashSet<usize>,
+    runner_options: &RunnerOptions,
+    jobs: usize,
+) -> Vec<MutantResult> {
+    let run = || {
+        mutations
+            .into_par_iter()
+            .map(|mutation| {
+                let start = Instant::now();
+                let outcome = if covered_functions.contains(&mutation.body_index()) {
+                    run_mutation(original_module_contents, &mutation, runner_options)
+                } else {
+                    MutantOutcome::NotCovered
+                };
+                MutantResult {
+                    mutation,
+                    outcome,
+                    duration: start.elapsed(),
+                }
+            })
+            .collect()
+    };
+    match ThreadPoolBuilder::new().num_threads(jobs).build() {
+        Ok(pool) => pool.install(run),
+        Err(_) => run(),
+    }
+}
+
+/// Returns the number of CPU cores available to run mutants on.
+pub fn default_jobs() -> usize {
+    thread::available_parallelism().map_or(1, |jobs| jobs.get())
+}
+
+fn run_mutation(
+    original_module_contents: &[u8],
+    mutation: &Mutation,
+    runner_options: &RunnerOptions,
+) -> MutantOutcome {
+    let result = load_module(original_module_contents).and_then(|mut mutated_module| {
+        mutation.perform(&mut mutated_module)?;
+        runner::run_module(mutated_module, runner_options)
+    });
+    match result {
+        Ok(TestResult::Passed) => MutantOutcome::Survived,
+        Ok(TestResult::Failed) => MutantOutcome::Killed,
+        Ok(TestResult::Trapped(kind)) => MutantOutcome::Trapped(kind),
+        Ok(TestResult::Timeout) => MutantOutcome::Timeout,
+        Err(_) => MutantOutcome::Unviable,
+    }
+}
+
+pub(crate) fn load_module(contents: &[u8]) -> Result<Module, Error> {
     deserialize_buffer::<Module>(contents)
-        .unwrap()
+        .map_err(|e| Error::Parse(e.to_string()))?
         .parse_names()
-        .unwrap()
+        .map_err(|(errors, _)| {
+            let messages: Vec<String> = errors.iter().map(|(_, e)| e.to_string()).collect();
+            Error::Parse(format!("invalid name section: {}", messages.join(", ")))
+        })
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::engine::find_surviving_mutants;
+    use crate::candidates::NamingSource;
+    use crate::engine::{find_surviving_mutants, MutantOutcome, MutationTestRun};
+    use crate::runner::TestResult;
+    use crate::test_support::{killable_function, module_with_functions, surviving_function};
+    use crate::{AcceptedMutants, ChangedLines, Error, MutationTestResults};
+    use parity_wasm::deserialize_buffer;
+    use parity_wasm::elements::{BlockType, CustomSection, Instruction, Module, Section};
 
     #[test]
     fn reports_no_surviving_mutants_when_no_mutants_found() {
         let module_contents = include_bytes!("res/cases/no_mutants.wasm");
 
-        let surviving_mutants = find_surviving_mutants(module_contents, &["no_mutants"], &[]);
+        let surviving_mutants =
+            find_surviving_mutants(module_contents, &["no_mutants"], &[]).unwrap();
 
         assert_eq!(surviving_mutants.len(), 0);
     }
 
     #[test]
     fn reports_no_surviving_mutants_when_all_mutants_killed() {
-        let module_contents = include_bytes!("res/cases/no_surviving_mutants.wasm");
+        let module_contents = module_with_functions(&[killable_function()]);
 
-        let surviving_mutants =
-            find_surviving_mutants(module_contents, &["no_surviving_mutants"], &[]);
+        let surviving_mutants = find_surviving_mutants(&module_contents, &["killed"], &[]).unwrap();
 
         assert_eq!(surviving_mutants.len(), 0);
     }
 
     #[test]
     fn reports_surviving_mutant_when_mutant_not_killed() {
-        let module_contents = include_bytes!("res/cases/surviving_mutants.wasm");
+        let module_contents = module_with_functions(&[surviving_function()]);
 
         let surviving_mutants =
-            find_surviving_mutants(module_contents, &["surviving_mutants"], &[]);
+            find_surviving_mutants(&module_contents, &["survivor"], &[]).unwrap();
+
+        assert!(!surviving_mutants.is_empty());
+    }
+
+    #[test]
+    fn fails_when_original_tests_fail() {
+        let module_contents = include_bytes!("res/cases/failing_tests.wasm");
+
+        let result = MutationTestRun::new(module_contents)
+            .include_module("failing_tests")
+            .run();
+
+        assert!(matches!(
+            result,
+            Err(Error::BaselineFailed(TestResult::Failed))
+        ));
+    }
+
+    #[test]
+    fn fails_on_invalid_module() {
+        let result = MutationTestRun::new(b"not a module").run();
+
+        assert!(matches!(result, Err(Error::Parse(_))));
+    }
+
+    #[test]
+    fn falls_back_to_export_names_without_names_section() {
+        let module_contents = crate::runner::tests::test_module(vec![Instruction::End]);
+
+        let results = MutationTestRun::new(&module_contents)
+            .include_module("main")
+            .run()
+            .unwrap();
+
+        assert_eq!(results.naming_source, NamingSource::Exports);
+    }
+
+    #[test]
+    fn records_baseline_measurements() {
+        let module_contents = module_with_functions(&[killable_function()]);
+
+        let results = MutationTestRun::new(&module_contents)
+            .include_module("killed")
+            .run()
+            .unwrap();
+
+        assert!(results.baseline.fuel_consumed > 0);
+    }
+
+    #[test]
+    fn applies_only_enabled_mutators() {
+        let module_contents = module_with_functions(&[killable_function()]);
+
+        let results = MutationTestRun::new(&module_contents)
+            .include_module("killed")
+            .enable_mutator("IfConditionToTrue")
+            .run()
+            .unwrap();
+
+        assert_eq!(results.mutants.len(), 1);
+        assert_eq!(results.mutants[0].mutation.describe(), "IfConditionToTrue");
+    }
+
+    #[test]
+    fn leaves_out_disabled_mutators() {
+        let module_contents = module_with_functions(&[killable_function()]);
+
+        let results = MutationTestRun::new(&module_contents)
+            .include_module("killed")
+            .enable_mutator("if-condition-to-false")
+            .enable_mutator("InstructionSwapping")
+            .disable_mutator("swap-i32-comparison")
+            .run()
+            .unwrap();
+
+        assert_eq!(results.mutants.len(), 1);
+        assert_eq!(results.mutants[0].mutation.describe(), "IfConditionToFalse");
+    }
+
+    #[test]
+    fn runs_only_selected_mutants() {
+        let module_contents = module_with_functions(&[killable_function()]);
+        let all_results = MutationTestRun::new(&module_contents)
+            .include_module("killed")
+            .run()
+            .unwrap();
+        let selected = all_results.mutants.last().unwrap().mutation.id();
+
+        let results = MutationTestRun::new(&module_contents)
+            .include_module("killed")
+            .select_mutant(selected)
+            .run()
+            .unwrap();
+
+        assert!(all_results.mutants.len() > 1);
+        assert_eq!(results.mutants.len(), 1);
+        assert_eq!(results.mutants[0].mutation.id(), selected);
+    }
+
+    #[test]
+    fn fails_on_selected_mutant_which_does_not_exist() {
+        let module_contents = module_with_functions(&[killable_function()]);
+
+        let result = MutationTestRun::new(&module_contents)
+            .include_module("killed")
+            .select_mutant("killed::check:IfConditionToTrue:00000000")
+            .run();
+
+        assert!(matches!(
+            result,
+            Err(Error::UnknownMutants(ids)) if ids == ["killed::check:IfConditionToTrue:00000000"]
+        ));
+    }
+
+    #[test]
+    fn reports_accepted_survivors_and_missing_accepted_mutants() {
+        let module_contents = module_with_functions(&[surviving_function()]);
+        let survivor = MutationTestRun::new(&module_contents)
+            .include_module("survivor")
+            .run()
+            .unwrap()
+            .surviving_mutants()
+            .next()
+            .unwrap()
+            .id()
+            .to_string();
+        let accepted = AcceptedMutants::parse(&format!(
+            "[[accepted]]\nid = \"{}\"\n\n[[accepted]]\nid = \"survivor::compute:IfConditionToTrue:0\"\n",
+            survivor
+        ))
+        .unwrap();
+
+        let results = MutationTestRun::new(&module_contents)
+            .include_module("survivor")
+            .accepted_mutants(accepted)
+            .run()
+            .unwrap();
+
+        let accepted = results
+            .mutants
+            .iter()
+            .find(|result| result.mutation.id() == survivor)
+            .unwrap();
+        assert_eq!(accepted.outcome, MutantOutcome::Accepted);
+        assert!(results
+            .surviving_mutants()
+            .all(|mutation| mutation.id() != survivor));
+        assert_eq!(results.missing_accepted.len(), 1);
+        assert_eq!(
+            results.missing_accepted[0].id,
+            "survivor::compute:IfConditionToTrue:0"
+        );
+    }
+
+    #[test]
+    fn does_not_report_accepted_mutants_of_mutators_which_were_not_applied() {
+        let module_contents = module_with_functions(&[killable_function()]);
+        let accepted =
+            AcceptedMutants::parse("[[accepted]]\nid = \"killed::check:InstructionSwapping:0\"\n")
+                .unwrap();
+
+        let results = MutationTestRun::new(&module_contents)
+            .include_module("killed")
+            .enable_mutator("IfConditionToTrue")
+            .accepted_mutants(accepted)
+            .run()
+            .unwrap();
+
+        assert!(!results.mutants.is_empty());
+        assert!(results.missing_accepted.is_empty());
+    }
+
+    #[test]
+    fn counts_all_tested_mutants() {
+        let module_contents = module_with_functions(&[killable_function()]);
+
+        let results = MutationTestRun::new(&module_contents)
+            .include_module("killed")
+            .run()
+            .unwrap();
+
+        assert!(!results.mutants.is_empty());
+        assert_eq!(results.totals().survived, 0);
+        assert_eq!(results.totals().total(), results.mutants.len());
+    }
+
+    #[test]
+    fn reports_mutants_in_functions_not_run_by_tests_as_not_covered() {
+        let module_contents = include_bytes!("res/cases/no_mutants.wasm");
+
+        let results = MutationTestRun::new(module_contents)
+            .include_module("core::fmt")
+            .default_excludes(false)
+            .run()
+            .unwrap();
+
+        assert!(results
+            .mutants
+            .iter()
+            .any(|result| result.outcome == MutantOutcome::NotCovered));
+        // Code of `core::fmt` inlined into functions which the tests run is covered.
+        assert!(results
+            .mutants
+            .iter()
+            .filter(|result| result.outcome == MutantOutcome::Survived)
+            .all(|result| result.mutation.inlined_into().is_some()));
+    }
+
+    #[test]
+    fn reports_mutant_which_never_terminates_as_timeout() {
+        let module_contents = module_with_functions(&[(
+            "spin::wait",
+            vec![
+                Instruction::Loop(BlockType::NoResult),
+                Instruction::I32Const(0),
+                Instruction::BrIf(0),
+                Instruction::End,
+                Instruction::End,
+            ],
+        )]);
+
+        let results = MutationTestRun::new(&module_contents)
+            .include_module("spin")
+            .enable_mutator("IfConditionToTrue")
+            .run()
+            .unwrap();
+
+        assert_eq!(results.mutants.len(), 1);
+        assert_eq!(results.mutants[0].outcome, MutantOutcome::Timeout);
+    }
+
+    #[test]
+    fn reports_same_results_whatever_the_number_of_jobs() {
+        let module_contents = module_with_functions(&[killable_function(), surviving_function()]);
+        let run = MutationTestRun::new(&module_contents)
+            .include_module("killed")
+            .include_module("survivor");
+
+        let sequential = run.clone().jobs(1).run().unwrap();
+        let parallel = run.jobs(4).run().unwrap();
+
+        let describe = |results: &MutationTestResults| -> Vec<String> {
+            results
+                .mutants
+                .iter()
+                .map(|result| format!("{:?} {:?}", result.mutation, result.outcome))
+                .collect()
+        };
+        assert_eq!(describe(&sequential), describe(&parallel));
+    }
+
+    #[test]
+    fn attaches_source_locations_to_mutations() {
+        let module_contents = include_bytes!("../runner/res/cases/test-example.wasm");
+
+        let results = MutationTestRun::new(module_contents)
+            .include_module("roman_numerals::to_roman")
+            .enable_mutator("IfConditionToTrue")
+            .run()
+            .unwrap();
+
+        assert!(!results.mutants.is_empty());
+        for result in &results.mutants {
+            let location = result.mutation.location().unwrap();
+            assert!(location.file.ends_with("src/lib.rs"));
+        }
+    }
+
+    #[test]
+    fn mutates_only_changed_lines() {
+        let module_contents = include_bytes!("../runner/res/cases/test-example.wasm");
+        let changed_lines =
+            ChangedLines::from_unified_diff("+++ b/src/lib.rs\n@@ -19 +19 @@\n+    }\n").unwrap();
+
+        let results = MutationTestRun::new(module_contents)
+            .include_module("roman_numerals")
+            .exclude_module("roman_numerals::tests")
+            .enable_mutator("IfConditionToTrue")
+            .changed_lines(changed_lines)
+            .run()
+            .unwrap();
+
+        assert!(!results.mutants.is_empty());
+        for result in &results.mutants {
+            assert_eq!(result.mutation.function_name(), "roman_numerals::to_roman");
+            assert_eq!(result.mutation.location().unwrap().line, 19);
+        }
+    }
+
+    #[test]
+    fn fails_on_selected_mutant_outside_changed_lines() {
+        let module_contents = include_bytes!("../runner/res/cases/test-example.wasm");
+        let run = || {
+            MutationTestRun::new(module_contents)
+                .include_module("roman_numerals::to_roman")
+                .enable_mutator("IfConditionToTrue")
+        };
+        let unchanged = run()
+            .run()
+            .unwrap()
+            .mutants
+            .into_iter()
+            .find(|result| result.mutation.location().unwrap().line != 19)
+            .unwrap();
+        let changed_lines =
+            ChangedLines::from_unified_diff("+++ b/src/lib.rs\n@@ -19 +19 @@\n+    }\n").unwrap();
+
+        let result = run()
+            .changed_lines(changed_lines)
+            .select_mutant(unchanged.mutation.id())
+            .run();
+
+        assert!(matches!(result, Err(Error::UnknownMutants(_))));
+    }
+
+    #[test]
+    fn attributes_mutants_in_inlined_code_to_inlined_function() {
+        let module_contents = include_bytes!("../runner/res/cases/test-example.wasm");
+
+        let results = MutationTestRun::new(module_contents)
+            .include_module("core::slice::Iter::post_inc_start")
+            .default_excludes(false)
+            .enable_mutator("IfConditionToTrue")
+            .run()
+            .unwrap();
+
+        assert!(!results.mutants.is_empty());
+        for result in &results.mutants {
+            let mutation = &result.mutation;
+            assert_eq!(
+                mutation.function_name(),
+                "core::slice::Iter<T>::post_inc_start"
+            );
+            assert!(mutation.inlined_into().unwrap().ends_with("::next"));
+        }
+    }
+
+    #[test]
+    fn skips_functions_listed_in_skip_section() {
+        let module_contents = module_with_functions(&[killable_function(), surviving_function()]);
+        let mut module = deserialize_buffer::<Module>(&module_contents).unwrap();
+        module
+            .sections_mut()
+            .push(Section::Custom(CustomSection::new(
+                String::from("mutants.skip"),
+                b"survivor::compute\0".to_vec(),
+            )));
+        let module_contents = module.to_bytes().unwrap();
+        let run = MutationTestRun::new(&module_contents)
+            .include_module("killed")
+            .include_module("survivor");
+
+        let results = run.clone().run().unwrap();
+        let unannotated_results = run.skip_annotations(false).run().unwrap();
+
+        assert!(results
+            .mutants
+            .iter()
+            .all(|result| result.mutation.function_name() == "killed::check"));
+        assert_eq!(unannotated_results.totals().survived, 1);
+    }
+
+    #[test]
+    fn fails_to_mutate_changed_lines_without_debug_information() {
+        let module_contents = module_with_functions(&[killable_function()]);
+
+        let result = MutationTestRun::new(&module_contents)
+            .include_module("killed")
+            .changed_lines(ChangedLines::default())
+            .run();
 
-        assert!(surviving_mutants.len() > 0);
+        assert!(matches!(result, Err(Error::MissingDebugInfo)));
     }
 }
@@ -0,0 +1,263 @@
+use crate::accepted::AcceptedMutant;
+use crate::candidates::NamingSource;
+use crate::mutation::Mutation;
+use crate::runner::TrapKind;
+use std::collections::BTreeMap;
+use std::fmt::{Display, Formatter, Result};
+use std::time::Duration;
+
+/// What happened when the tests were run against a mutant.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum MutantOutcome {
+    /// A test failed.
+    Killed,
+    /// All tests passed.
+    Survived,
+    /// All tests passed, but the mutant is listed as accepted, e.g. because it is equivalent to
+    /// the original code.
+    Accepted,
+    /// The tests did not finish within their time budget.
+    Timeout,
+    /// Execution stopped on a trap other than that raised by a failing test.
+    Trapped(TrapKind),
+    /// The mutated module could not be loaded or run.
+    Unviable,
+    /// The tests never enter the mutated function, so the mutant was not run.
+    NotCovered,
+}
+
+impl MutantOutcome {
+    /// Short identifier of the outcome, as used in reports.
+    pub fn name(&self) -> &'static str {
+        match self {
+            MutantOutcome::Killed => "killed",
+            MutantOutcome::Survived => "survived",
+            MutantOutcome::Accepted => "accepted",
+            MutantOutcome::Timeout => "timeout",
+            MutantOutcome::Trapped(_) => "trapped",
+            MutantOutcome::Unviable => "unviable",
+            MutantOutcome::NotCovered => "not_covered",
+        }
+    }
+
+    /// How the tests detected the mutation, if they did.
+    pub fn kill_reason(&self) -> Option<String> {
+        match self {
+            MutantOutcome::Killed => Some(String::from("a test failed")),
+            MutantOutcome::Timeout => Some(String::from("the tests ran out of fuel")),
+            MutantOutcome::Trapped(kind) => Some(format!("the tests trapped with {:?}", kind)),
+            _ => None,
+        }
+    }
+
+    /// Whether the tests noticed the mutation.
+    pub fn is_detected(&self) -> bool {
+        matches!(
+            self,
+            MutantOutcome::Killed | MutantOutcome::Timeout | MutantOutcome::Trapped(_)
+        )
+    }
+}
+
+#[derive(Debug)]
+pub struct MutantResult {
+    pub mutation: Mutation,
+    pub outcome: MutantOutcome,
+    /// Time taken to run the tests against the mutant, or zero if they were not run.
+    pub duration: Duration,
+}
+
+/// Number of mutants with each outcome.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct Totals {
+    pub killed: usize,
+    pub survived: usize,
+    pub accepted: usize,
+    pub timeout: usize,
+    pub trapped: usize,
+    pub unviable: usize,
+    pub not_covered: usize,
+}
+
+impl Totals {
+    pub(crate) fn add(&mut self, outcome: MutantOutcome) {
+        match outcome {
+            MutantOutcome::Killed => self.killed += 1,
+            MutantOutcome::Survived => self.survived += 1,
+            MutantOutcome::Accepted => self.accepted += 1,
+            MutantOutcome::Timeout => self.timeout += 1,
+            MutantOutcome::Trapped(_) => self.trapped += 1,
+            MutantOutcome::Unviable => self.unviable += 1,
+            MutantOutcome::NotCovered => self.not_covered += 1,
+        }
+    }
+
+    pub fn total(&self) -> usize {
+        self.detected() + self.survived + self.accepted + self.unviable + self.not_covered
+    }
+
+    pub fn detected(&self) -> usize {
+        self.killed + self.timeout + self.trapped
+    }
+
+    /// Percentage of viable mutants which the tests detected, counting mutants in code which the
+    /// tests never run as undetected. Accepted mutants are left out like unviable ones. `None` if
+    /// there are no viable mutants.
+    pub fn mutation_score(&self) -> Option<f64> {
+        let viable = self.total() - self.unviable - self.accepted;
+        if viable == 0 {
+            None
+        } else {
+            Some(100.0 * self.detected() as f64 / viable as f64)
+        }
+    }
+}
+
+impl Display for Totals {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        write!(
+            f,
+            "{} mutants: {} killed, {} survived",
+            self.total(),
+            self.killed,
+            self.survived
+        )?;
+        if self.accepted > 0 {
+            write!(f, " ({} accepted)", self.accepted)?;
+        }
+        write!(
+            f,
+            ", {} timed out, {} trapped, {} unviable, {} not covered",
+            self.timeout, self.trapped, self.unviable, self.not_covered
+        )?;
+        match self.mutation_score() {
+            Some(score) => write!(f, "; mutation score {:.1}%", score),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Measurements of the run of the original, unmutated tests.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct Baseline {
+    pub duration: Duration,
+    /// Approximate number of instructions which the tests executed.
+    pub fuel_consumed: u64,
+}
+
+/// Outcome of a mutation testing run.
+#[derive(Debug, Default)]
+pub struct MutationTestResults {
+    /// Where the names of the functions of the module were found.
+    pub naming_source: NamingSource,
+    pub baseline: Baseline,
+    /// Every mutant which was found, in the order in which they were found.
+    pub mutants: Vec<MutantResult>,
+    /// Accepted mutants of the mutated functions which no longer exist, e.g. because the code
+    /// was changed, and should be removed from the list.
+    pub missing_accepted: Vec<AcceptedMutant>,
+}
+
+impl MutationTestResults {
+    pub fn surviving_mutants(&self) -> impl Iterator<Item = &Mutation> {
+        self.mutants
+            .iter()
+            .filter(|result| result.outcome == MutantOutcome::Survived)
+            .map(|result| &result.mutation)
+    }
+
+    pub fn totals(&self) -> Totals {
+        let mut totals = Totals::default();
+        for result in &self.mutants {
+            totals.add(result.outcome);
+        }
+        totals
+    }
+
+    /// Totals for each mutated function, keyed by function index.
+    pub fn totals_by_function(&self) -> BTreeMap<usize, Totals> {
+        let mut totals = BTreeMap::<usize, Totals>::new();
+        for result in &self.mutants {
+            totals
+                .entry(result.mutation.function_index())
+                .or_default()
+                .add(result.outcome);
+        }
+        totals
+    }
+
+    pub fn mutation_score(&self) -> Option<f64> {
+        self.totals().mutation_score()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::engine::results::{MutantOutcome, Totals};
+    use crate::runner::TrapKind;
+
+    #[test]
+    fn displays_totals_with_mutation_score() {
+        let mut totals = Totals::default();
+        totals.add(MutantOutcome::Killed);
+        totals.add(MutantOutcome::Survived);
+        totals.add(MutantOutcome::Unviable);
+
+        assert_eq!(
+            totals.to_string(),
+            "3 mutants: 1 killed, 1 survived, 0 timed out, 0 trapped, 1 unviable, 0 not covered; \
+             mutation score 50.0%"
+        );
+    }
+
+    #[test]
+    fn mutation_score_counts_detected_mutants() {
+        let mut totals = Totals::default();
+        totals.add(MutantOutcome::Killed);
+        totals.add(MutantOutcome::Trapped(TrapKind::DivisionByZero));
+        totals.add(MutantOutcome::Timeout);
+        totals.add(MutantOutcome::Survived);
+
+        assert_eq!(totals.mutation_score(), Some(75.0));
+    }
+
+    #[test]
+    fn mutation_score_counts_uncovered_mutants_as_undetected() {
+        let mut totals = Totals::default();
+        totals.add(MutantOutcome::Killed);
+        totals.add(MutantOutcome::NotCovered);
+
+        assert_eq!(totals.mutation_score(), Some(50.0));
+    }
+
+    #[test]
+    fn mutation_score_ignores_unviable_mutants() {
+        let mut totals = Totals::default();
+        totals.add(MutantOutcome::Killed);
+        totals.add(MutantOutcome::Unviable);
+
+        assert_eq!(totals.mutation_score(), Some(100.0));
+    }
+
+    #[test]
+    fn mutation_score_ignores_accepted_mutants() {
+        let mut totals = Totals::default();
+        totals.add(MutantOutcome::Killed);
+        totals.add(MutantOutcome::Accepted);
+
+        assert_eq!(totals.mutation_score(), Some(100.0));
+        assert_eq!(
+            totals.to_string(),
+            "2 mutants: 1 killed, 0 survived (1 accepted), 0 timed out, 0 trapped, 0 unviable, \
+             0 not covered; mutation score 100.0%"
+        );
+    }
+
+    #[test]
+    fn mutation_score_is_none_without_viable_mutants() {
+        let mut totals = Totals::default();
+        totals.add(MutantOutcome::Unviable);
+
+        assert_eq!(totals.mutation_score(), None);
+    }
+}
@@ -0,0 +1,110 @@
+use crate::runner::TestResult;
+use std::fmt::{Display, Formatter};
+
+/// Error which prevents a mutation testing run from producing results.
+#[derive(Debug)]
+pub enum Error {
+    /// The input is not a valid WebAssembly module.
+    Parse(String),
+    /// Neither the name section, the linking section, the debugging information nor the exports
+    /// of the module name any function, so functions cannot be selected by name.
+    NoFunctionNames,
+    /// The module has no code section, so there is no code to mutate.
+    MissingCodeSection,
+    /// A function index does not refer to a function defined in the module.
+    InvalidFunctionIndex(usize),
+    /// The module could not be instantiated, e.g. because it has imports which cannot be resolved.
+    Instantiation(String),
+    /// The module does not export a function with the given name which runs the tests.
+    MissingEntryExport(String),
+    /// The function exported under the given name to run the tests does not take the two `i32`
+    /// arguments of the `main` function of a test binary.
+    EntryPointSignature(String),
+    /// A mutated module could not be serialized.
+    Serialization(String),
+    /// An include or exclude pattern is not valid. Holds the pattern and the reason.
+    InvalidPattern(String, String),
+    /// The changed lines to which mutation is restricted could not be read.
+    Diff(String),
+    /// Mutation is restricted to changed lines, but the module has no line table to map its code
+    /// to source lines.
+    MissingDebugInfo,
+    /// The original, unmutated tests did not pass, so mutants cannot be judged against them.
+    BaselineFailed(TestResult),
+    /// The results could not be serialized into a machine-readable report.
+    Report(String),
+    /// No mutant of the selected functions and lines has the given IDs, which were selected to be
+    /// run.
+    UnknownMutants(Vec<String>),
+    /// No mutator can be enabled or disabled by the given name.
+    UnknownMutator(String),
+    /// A minimum mutation score is not valid.
+    InvalidThreshold(String),
+    /// The configuration file could not be read or is not valid.
+    Config(String),
+    /// The file of accepted surviving mutants could not be parsed.
+    AcceptedMutants(String),
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::Parse(message) => write!(f, "not a valid WebAssembly module: {}", message),
+            Error::NoFunctionNames => write!(
+                f,
+                "the module names none of its functions; build it with a name section or debug \
+                 information"
+            ),
+            Error::MissingCodeSection => write!(f, "the module contains no code"),
+            Error::InvalidFunctionIndex(index) => {
+                write!(f, "the module defines no function with index {}", index)
+            }
+            Error::Instantiation(message) => {
+                write!(f, "the module could not be instantiated: {}", message)
+            }
+            Error::MissingEntryExport(name) => {
+                write!(f, "the module does not export a function `{}`", name)
+            }
+            Error::EntryPointSignature(name) => write!(
+                f,
+                "the exported function `{}` does not take two i32 arguments",
+                name
+            ),
+            Error::Serialization(message) => {
+                write!(f, "a mutated module could not be serialized: {}", message)
+            }
+            Error::InvalidPattern(pattern, message) => {
+                write!(f, "invalid pattern `{}`: {}", pattern, message)
+            }
+            Error::Diff(message) => write!(f, "could not read the changed lines: {}", message),
+            Error::MissingDebugInfo => write!(
+                f,
+                "the module has no line table to find the changed code; build it with debug \
+                 information"
+            ),
+            Error::BaselineFailed(result) => write!(
+                f,
+                "the original tests must pass before mutants can be tested, but they ended with \
+                 {:?}",
+                result
+            ),
+            Error::Report(message) => write!(f, "the report could not be serialized: {}", message),
+            Error::UnknownMutants(ids) => {
+                write!(f, "no mutant has the ID {}", ids.join(", "))
+            }
+            Error::UnknownMutator(name) => write!(
+                f,
+                "unknown mutator `{}`, expected one of {}",
+                name,
+                crate::mutation::mutator_names().join(", ")
+            ),
+            Error::Config(message) => write!(f, "invalid configuration: {}", message),
+            Error::InvalidThreshold(message) => write!(f, "invalid threshold: {}", message),
+            Error::AcceptedMutants(message) => {
+                write!(f, "could not read the accepted mutants: {}", message)
+            }
+        }
+    }
+}
+
+impl std::error::Error for Error {}
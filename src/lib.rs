@@ -0,0 +1,35 @@
+//! Mutation testing for Rust code compiled to WebAssembly.
+//!
+//! The tests of a crate are built into a WebAssembly module. The engine then selects candidate
+//! functions from the module by name, applies small changes (mutations) to their code and runs the
+//! tests against each mutated module. Mutants for which the tests still pass point to behaviour
+//! which the tests do not check.
+//!
+//! Use [`MutationTestRun`] to configure and perform a run.
+
+pub mod accepted;
+pub mod candidates;
+pub mod cli;
+pub mod config;
+mod debug_info;
+pub mod diff;
+pub mod engine;
+mod error;
+pub mod mutation;
+pub mod report;
+pub mod runner;
+mod skip;
+#[cfg(test)]
+mod test_support;
+pub mod threshold;
+
+pub use accepted::AcceptedMutants;
+pub use config::Config;
+pub use debug_info::SourceLocation;
+pub use diff::ChangedLines;
+pub use engine::{
+    Baseline, MutantOutcome, MutantResult, MutationTestResults, MutationTestRun, Totals,
+};
+pub use error::Error;
+pub use runner::RunnerOptions;
+pub use threshold::Threshold;
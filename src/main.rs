@@ -1,19 +1,206 @@
-mod candidates;
-mod engine;
-mod mutation;
-mod runner;
+use clap::{Parser, Subcommand, ValueEnum};
+use mutation_test_engine::{
+    cli::CommonOptions,
+    mutation::{self, Mutation},
+    report, threshold,
+    threshold::FailedThreshold,
+    AcceptedMutants, ChangedLines, Config, MutationTestResults, MutationTestRun,
+};
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process;
+
+const EXIT_ERROR: i32 = 1;
+const EXIT_BELOW_THRESHOLD: i32 = 3;
+
+/// Runs the tests in a WebAssembly module against mutated versions of its code and reports the
+/// mutants which the tests fail to detect.
+#[derive(Parser, Debug)]
+#[command(
+    version,
+    subcommand_negates_reqs = true,
+    args_conflicts_with_subcommands = true
+)]
+struct Options {
+    #[command(subcommand)]
+    command: Option<Command>,
+
+    /// Path to the WebAssembly module containing the code under test and its tests
+    #[arg(required = true)]
+    module: Option<PathBuf>,
+
+    #[command(flatten)]
+    common: CommonOptions,
+
+    /// Write the report to this file instead of standard output
+    #[arg(short, long, value_name = "FILE")]
+    output: Option<PathBuf>,
+
+    /// Format of the report [default: text]
+    #[arg(long, value_enum)]
+    format: Option<Format>,
+
+    /// Do not report the result of running the original, unmutated tests
+    #[arg(short, long)]
+    quiet: bool,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// List the mutators which can be enabled or disabled, with their IDs and kinds
+    ListMutators,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+enum Format {
+    /// A summary followed by the surviving mutants
+    Text,
+    /// Every mutant with its metadata, for other tools
+    Json,
+    /// A self-contained page showing the code of each mutated function, for browsing
+    Html,
+}
+
+impl Options {
+    /// Fills in the settings which were not given as flags from the configuration file.
+    fn merge(&mut self, config: Config) -> Result<(), String> {
+        self.common.merge(&config).map_err(|e| e.to_string())?;
+        if self.format.is_none() {
+            self.format = config
+                .report
+                .format
+                .map(|format| Format::from_str(&format, true))
+                .transpose()?;
+        }
+        self.output = self.output.take().or(config.report.output);
+        Ok(())
+    }
+}
 
 fn main() {
-    println!(
-        "Original tests result: {:?}",
-        runner::run_tests(include_bytes!("test-example.wasm"))
-    );
-    println!(
-        "Surviving mutants: {:?}",
-        engine::find_surviving_mutants(
-            include_bytes!("test-example.wasm"),
-            &vec!["roman_numerals"],
-            &vec!["roman_numerals::tests"],
-        )
-    );
+    let mut options = Options::parse();
+    if let Some(Command::ListMutators) = options.command {
+        print!("{}", mutation::list_mutators());
+        return;
+    }
+    if let Err(message) = std::env::current_dir()
+        .map_err(|e| format!("could not find the working directory: {}", e))
+        .and_then(|directory| {
+            Config::load(options.common.config.as_deref(), &directory).map_err(|e| e.to_string())
+        })
+        .and_then(|config| options.merge(config))
+    {
+        eprintln!("error: {}", message);
+        process::exit(EXIT_ERROR);
+    }
+    match run(&options) {
+        Ok(failed) if failed.is_empty() => {}
+        Ok(failed) => {
+            for threshold in failed {
+                eprintln!("error: {}", threshold);
+            }
+            process::exit(EXIT_BELOW_THRESHOLD);
+        }
+        Err(message) => {
+            eprintln!("error: {}", message);
+            process::exit(EXIT_ERROR);
+        }
+    }
+}
+
+/// Runs the mutants and writes the report, returning the thresholds which were not reached.
+fn run(options: &Options) -> Result<Vec<FailedThreshold>, String> {
+    let module = options
+        .module
+        .as_deref()
+        .ok_or("no module to test was given")?;
+    let contents =
+        fs::read(module).map_err(|e| format!("could not read {}: {}", module.display(), e))?;
+
+    let common = &options.common;
+    let mut run = MutationTestRun::new(&contents);
+    if let Some(changed_lines) =
+        read_changed_lines(common.diff.as_deref(), common.git_base.as_deref())?
+    {
+        run = run.changed_lines(changed_lines);
+    }
+    if let Some(path) = &common.accepted {
+        run = run.accepted_mutants(AcceptedMutants::read(path).map_err(|e| e.to_string())?);
+    }
+    let results = common
+        .configure(run.include_modules(&common.include))
+        .run()
+        .map_err(|e| format!("{}: {}", module.display(), e))?;
+    for mutant in &results.missing_accepted {
+        eprintln!("warning: accepted mutant {} no longer exists", mutant.id);
+    }
+
+    let report = match options.format.unwrap_or(Format::Text) {
+        Format::Text => {
+            let diffs = if common.show_diffs {
+                report::surviving_mutant_diffs(&results, &contents)
+                    .map_err(|e| format!("{}: {}", module.display(), e))?
+            } else {
+                Vec::new()
+            };
+            text_report(&results, &diffs, options.quiet)
+        }
+        Format::Json => report::json_report(&results, &contents)
+            .map(|json| json + "\n")
+            .map_err(|e| format!("{}: {}", module.display(), e))?,
+        Format::Html => report::html_report(&results, &contents)
+            .map_err(|e| format!("{}: {}", module.display(), e))?,
+    };
+
+    match &options.output {
+        Some(path) => fs::write(path, report)
+            .map_err(|e| format!("could not write {}: {}", path.display(), e))?,
+        None => std::io::stdout()
+            .write_all(report.as_bytes())
+            .map_err(|e| format!("could not write report: {}", e))?,
+    }
+    Ok(threshold::failed_thresholds(&results, &common.min_score))
+}
+
+fn text_report(
+    results: &MutationTestResults,
+    diffs: &[(&Mutation, String)],
+    quiet: bool,
+) -> String {
+    let mut report = String::new();
+    if !quiet {
+        report.push_str(&format!(
+            "Function names read from {}\n",
+            results.naming_source
+        ));
+        report.push_str(&format!(
+            "Original tests passed in {:.2?}, executing about {} instructions\n",
+            results.baseline.duration, results.baseline.fuel_consumed
+        ));
+    }
+    let surviving_mutants: Vec<_> = results.surviving_mutants().collect();
+    report.push_str(&format!("Surviving mutants: {:?}\n", surviving_mutants));
+    for (mutation, diff) in diffs {
+        report.push_str(&format!("{:?}\n{}", mutation, diff));
+    }
+    report.push_str(&format!("{}\n", results.totals()));
+    report
+}
+
+fn read_changed_lines(
+    diff: Option<&Path>,
+    git_base: Option<&str>,
+) -> Result<Option<ChangedLines>, String> {
+    if let Some(path) = diff {
+        ChangedLines::read(path)
+            .map(Some)
+            .map_err(|e| e.to_string())
+    } else if let Some(base) = git_base {
+        ChangedLines::from_git(Path::new("."), base)
+            .map(Some)
+            .map_err(|e| e.to_string())
+    } else {
+        Ok(None)
+    }
 }
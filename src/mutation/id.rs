@@ -0,0 +1,181 @@
+//! Identifiers of mutants which stay the same when the module is rebuilt.
+//!
+//! Function and instruction indices shift whenever code is added to or removed from the module, so
+//! a mutant is instead identified by the demangled path of its function, the name of its mutator
+//! and a hash of the change it makes and of the instructions around it, e.g.
+//! `roman_numerals::to_roman:InstructionSwapping:ec784069`. Mutants which only differ in their
+//! position are told apart by a suffix counting those before them, e.g. `...:ec784069-1`.
+
+use crate::mutation::Mutation;
+use parity_wasm::elements::{FuncBody, Instruction};
+use std::collections::HashMap;
+
+/// Number of instructions on either side of the mutated one which are hashed into its ID.
+const CONTEXT: usize = 3;
+
+const FNV_OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+
+/// Assigns an ID to each of the given mutations of the given bodies. The mutations must be in the
+/// order in which they were found, so that those which only differ in their position are numbered
+/// in the order of their instructions.
+pub(crate) fn assign_ids(mutations: &mut [Mutation], bodies: &[FuncBody]) {
+    let mut occurrences = HashMap::<String, usize>::new();
+    for mutation in mutations {
+        let context = bodies.get(mutation.body_index).map_or(&[][..], |body| {
+            let instructions = body.code().elements();
+            let start = mutation.instruction_index.saturating_sub(CONTEXT);
+            let end = (mutation.instruction_index + CONTEXT + 1).min(instructions.len());
+            instructions.get(start..end).unwrap_or_default()
+        });
+        let id = format!(
+            "{}:{}:{:08x}",
+            mutation.function_name,
+            mutation.mutator.name(),
+            context_hash(mutation, context)
+        );
+        let occurrence = occurrences.entry(id.clone()).or_default();
+        mutation.id = match *occurrence {
+            0 => id,
+            n => format!("{}-{}", id, n),
+        };
+        *occurrence += 1;
+    }
+}
+
+/// Hashes the description of the mutation, the function into which its code was inlined, if any,
+/// and the instructions around it. Operands which change when unrelated code or data is added to
+/// the module are left out: the indices of called functions, types and globals, and `i32`
+/// constants, which are often addresses of static data or of the stack.
+fn context_hash(mutation: &Mutation, context: &[Instruction]) -> u32 {
+    let mut hash = FNV_OFFSET_BASIS;
+    let mut write = |text: &str| {
+        for byte in text.bytes().chain(std::iter::once(0)) {
+            hash = (hash ^ u64::from(byte)).wrapping_mul(FNV_PRIME);
+        }
+    };
+    write(&mutation.describe());
+    write(mutation.inlined_into.as_deref().unwrap_or_default());
+    for instruction in context {
+        match instruction {
+            Instruction::Call(_) => write("call"),
+            Instruction::CallIndirect(_, _) => write("call_indirect"),
+            Instruction::GetGlobal(_) => write("get_global"),
+            Instruction::SetGlobal(_) => write("set_global"),
+            Instruction::I32Const(_) => write("i32.const"),
+            instruction => write(&instruction.to_string()),
+        }
+    }
+    (hash >> 32) as u32 ^ hash as u32
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::mutation::id::assign_ids;
+    use crate::mutation::{enabled_mutators, Mutation};
+    use parity_wasm::elements::{FuncBody, Instruction, Instructions};
+
+    fn mutations_of(name: &str, instructions: Vec<Instruction>) -> (Vec<Mutation>, FuncBody) {
+        let body = FuncBody::new(Vec::new(), Instructions::new(instructions));
+        let mutators = enabled_mutators(&[String::from("IfConditionToTrue")], &[]);
+        let mutations = mutators
+            .iter()
+            .flat_map(|mutator| mutator.find(&body, 0))
+            .map(|mutation| mutation.in_function(0, name))
+            .collect();
+        (mutations, body)
+    }
+
+    fn ids(name: &str, instructions: Vec<Instruction>) -> Vec<String> {
+        let (mut mutations, body) = mutations_of(name, instructions);
+        assign_ids(&mut mutations, &[body]);
+        mutations
+            .iter()
+            .map(|mutation| String::from(mutation.id()))
+            .collect()
+    }
+
+    #[test]
+    fn identifies_mutants_by_function_mutator_and_context() {
+        let ids = ids(
+            "branch::taken",
+            vec![
+                Instruction::I32Const(1),
+                Instruction::BrIf(0),
+                Instruction::End,
+            ],
+        );
+
+        assert_eq!(ids.len(), 1);
+        assert!(ids[0].starts_with("branch::taken:IfConditionToTrue:"));
+    }
+
+    #[test]
+    fn keeps_ids_when_code_outside_context_or_called_functions_change() {
+        let original = ids(
+            "branch::taken",
+            vec![
+                Instruction::I32Const(1),
+                Instruction::I32Const(2),
+                Instruction::Call(3),
+                Instruction::BrIf(0),
+                Instruction::End,
+            ],
+        );
+
+        let shifted = ids(
+            "branch::taken",
+            vec![
+                Instruction::Nop,
+                Instruction::Nop,
+                Instruction::I32Const(1),
+                Instruction::I32Const(2),
+                Instruction::Call(4),
+                Instruction::BrIf(0),
+                Instruction::End,
+            ],
+        );
+
+        assert_eq!(original, shifted);
+    }
+
+    #[test]
+    fn keeps_ids_when_addresses_globals_or_types_change() {
+        let original = ids(
+            "branch::indirect",
+            vec![
+                Instruction::GetGlobal(0),
+                Instruction::I32Const(1_048_576),
+                Instruction::CallIndirect(1, 0),
+                Instruction::BrIf(0),
+                Instruction::SetGlobal(1),
+                Instruction::End,
+            ],
+        );
+
+        let relocated = ids(
+            "branch::indirect",
+            vec![
+                Instruction::GetGlobal(2),
+                Instruction::I32Const(1_048_592),
+                Instruction::CallIndirect(3, 0),
+                Instruction::BrIf(0),
+                Instruction::SetGlobal(4),
+                Instruction::End,
+            ],
+        );
+
+        assert_eq!(original, relocated);
+    }
+
+    #[test]
+    fn numbers_mutants_with_the_same_context() {
+        let branches = vec![Instruction::BrIf(0); 10];
+
+        let ids = ids("branch::repeated", branches);
+
+        assert_ne!(ids[2], ids[3]);
+        assert_eq!(ids[4], format!("{}-1", ids[3]));
+        assert_eq!(ids[5], format!("{}-2", ids[3]));
+    }
+}
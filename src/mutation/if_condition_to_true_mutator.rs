@@ -14,14 +14,11 @@ impl Mutator for IfConditionToTrueMutator {
         }
     }
 
-    fn find(&self, body: &FuncBody, function_index: usize) -> Vec<Mutation> {
+    fn find(&self, body: &FuncBody, body_index: usize) -> Vec<Mutation> {
         let mut result = Vec::<Mutation>::new();
         for (instruction_index, instruction) in body.code().elements().iter().enumerate() {
-            match *instruction {
-                Instruction::BrIf(_) => {
-                    result.push(self.create_mutation(function_index, instruction_index))
-                }
-                _ => {}
+            if let Instruction::BrIf(_) = *instruction {
+                result.push(self.create_mutation(body_index, instruction_index))
             }
         }
         result
@@ -30,15 +27,15 @@ impl Mutator for IfConditionToTrueMutator {
     fn describe(&self) -> String {
         String::from("IfConditionToTrue")
     }
+
+    fn name(&self) -> &'static str {
+        "IfConditionToTrue"
+    }
 }
 
 impl IfConditionToTrueMutator {
-    fn create_mutation(&self, function_index: usize, instruction_index: usize) -> Mutation {
-        Mutation {
-            mutator: Box::new(self.clone()),
-            instruction_index,
-            function_index,
-        }
+    fn create_mutation(&self, body_index: usize, instruction_index: usize) -> Mutation {
+        Mutation::new(Box::new(self.clone()), body_index, instruction_index)
     }
 }
 
@@ -163,7 +160,7 @@ mod tests {
 
         let result = subject.find(&module.code_section().unwrap().bodies()[0], 0);
 
-        result[0].perform(&mut module);
+        result[0].perform(&mut module).unwrap();
         assert_eq!(
             module.code_section().unwrap().bodies()[0].code().elements(),
             vec![Instruction::Drop, Instruction::Br(0)]
@@ -187,7 +184,7 @@ mod tests {
 
         let result = subject.find(&module.code_section().unwrap().bodies()[0], 0);
 
-        result[0].perform(&mut module);
+        result[0].perform(&mut module).unwrap();
         assert_eq!(
             module.code_section().unwrap().bodies()[0].code().elements(),
             vec![Instruction::I32Add, Instruction::Drop, Instruction::Br(0)]
@@ -210,7 +207,7 @@ mod tests {
 
         let result = subject.find(&module.code_section().unwrap().bodies()[1], 1);
 
-        result[0].perform(&mut module);
+        result[0].perform(&mut module).unwrap();
         assert_eq!(
             module.code_section().unwrap().bodies()[1].code().elements(),
             vec![Instruction::Drop, Instruction::Br(0)]
@@ -235,7 +232,7 @@ mod tests {
         let result = subject.find(&module.code_section().unwrap().bodies()[0], 0);
 
         for mutation in result.iter().rev() {
-            mutation.perform(&mut module);
+            mutation.perform(&mut module).unwrap();
         }
         assert_eq!(
             module.code_section().unwrap().bodies()[0].code().elements(),
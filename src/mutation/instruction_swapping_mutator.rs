@@ -13,15 +13,15 @@ impl Mutator for InstructionSwappingMutator {
         body.code_mut().elements_mut()[index] = self.replacement_instruction.clone();
     }
 
-    fn find(&self, body: &FuncBody, function_index: usize) -> Vec<Mutation> {
+    fn find(&self, body: &FuncBody, body_index: usize) -> Vec<Mutation> {
         let mut result = Vec::<Mutation>::new();
         for (instruction_index, instruction) in body.code().elements().iter().enumerate() {
             if *instruction == self.original_instruction {
-                result.push(Mutation {
-                    mutator: Box::new(self.clone()),
+                result.push(Mutation::new(
+                    Box::new(self.clone()),
+                    body_index,
                     instruction_index,
-                    function_index,
-                });
+                ));
             }
         }
         result
@@ -33,6 +33,10 @@ impl Mutator for InstructionSwappingMutator {
             self.original_instruction, self.replacement_instruction
         )
     }
+
+    fn name(&self) -> &'static str {
+        "InstructionSwapping"
+    }
 }
 
 #[cfg(test)]
@@ -125,7 +129,7 @@ mod tests {
 
         let result = subject.find(&module.code_section().unwrap().bodies()[0], 0);
 
-        result[0].perform(&mut module);
+        result[0].perform(&mut module).unwrap();
         assert_eq!(
             module.code_section().unwrap().bodies()[0].code().elements(),
             vec![Instruction::I32GtU]
@@ -152,7 +156,7 @@ mod tests {
 
         let result = subject.find(&module.code_section().unwrap().bodies()[0], 0);
 
-        result[0].perform(&mut module);
+        result[0].perform(&mut module).unwrap();
         assert_eq!(
             module.code_section().unwrap().bodies()[0].code().elements(),
             vec![Instruction::I32Add, Instruction::I32GtU]
@@ -178,7 +182,7 @@ mod tests {
 
         let result = subject.find(&module.code_section().unwrap().bodies()[1], 1);
 
-        result[0].perform(&mut module);
+        result[0].perform(&mut module).unwrap();
         assert_eq!(
             module.code_section().unwrap().bodies()[1].code().elements(),
             vec![Instruction::I32GtU]
@@ -206,7 +210,7 @@ mod tests {
         let result = subject.find(&module.code_section().unwrap().bodies()[0], 0);
 
         for mutation in result {
-            mutation.perform(&mut module);
+            mutation.perform(&mut module).unwrap();
         }
         assert_eq!(
             module.code_section().unwrap().bodies()[0].code().elements(),
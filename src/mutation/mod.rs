@@ -1,22 +1,212 @@
-mod all_mutators;
+mod id;
 mod if_condition_to_false_mutator;
 mod if_condition_to_true_mutator;
 mod instruction_swapping_mutator;
+#[allow(clippy::module_inception)]
 pub mod mutation;
 mod mutator;
+mod registry;
 mod set_cancelling_mutator;
 
-pub use mutation::Mutation;
+use crate::candidates::CandidateFunction;
+use crate::Error;
+pub(crate) use id::assign_ids;
+pub use mutation::{Mutation, Replacement};
 use mutator::Mutator;
 use parity_wasm::elements::FuncBody;
+pub use registry::RegisteredMutator;
 
-pub(crate) fn find_mutations(indices: Vec<usize>, bodies: &[FuncBody]) -> Vec<Mutation> {
+pub(crate) type StaticMutator = &'static (dyn Mutator + 'static);
+
+/// Returns the mutators registered under the given IDs or kinds, or the mutators enabled by
+/// default if none are given, leaving out those registered under the disabled IDs or kinds.
+pub(crate) fn enabled_mutators(enabled: &[String], disabled: &[String]) -> Vec<StaticMutator> {
+    let named = |names: &[String], registered: &RegisteredMutator| {
+        names.iter().any(|name| registered.is_named(name))
+    };
+    registry::MUTATORS
+        .iter()
+        .filter(|registered| {
+            if enabled.is_empty() {
+                registered.enabled_by_default
+            } else {
+                named(enabled, registered)
+            }
+        })
+        .filter(|registered| !named(disabled, registered))
+        .flat_map(|registered| registered.mutators().iter().copied())
+        .collect()
+}
+
+/// Returns all registered mutators, in the order in which they are applied.
+pub fn registered_mutators() -> &'static [RegisteredMutator] {
+    &registry::MUTATORS
+}
+
+/// Returns the names by which mutators can be enabled or disabled: the ID of each registered
+/// mutator, followed by the kinds, which select all mutators of a kind at once.
+pub fn mutator_names() -> Vec<&'static str> {
+    let mut names: Vec<&'static str> = registry::MUTATORS
+        .iter()
+        .map(|registered| registered.id)
+        .collect();
+    for registered in &registry::MUTATORS {
+        if !names.contains(&registered.kind()) {
+            names.push(registered.kind());
+        }
+    }
+    names
+}
+
+/// Checks that mutators can be enabled or disabled by the given name (see [`mutator_names`]).
+pub fn mutator_name(name: &str) -> Result<String, Error> {
+    if mutator_names().contains(&name) {
+        Ok(String::from(name))
+    } else {
+        Err(Error::UnknownMutator(String::from(name)))
+    }
+}
+
+/// Lists each registered mutator with its kind and description, aligned in columns.
+pub fn list_mutators() -> String {
+    let mutators = registered_mutators();
+    let id_width = mutators.iter().map(|mutator| mutator.id.len()).max();
+    let kind_width = mutators.iter().map(|mutator| mutator.kind().len()).max();
+    let mut list = String::new();
+    for mutator in mutators {
+        list.push_str(&format!(
+            "{:id_width$}  {:kind_width$}  {}{}\n",
+            mutator.id,
+            mutator.kind(),
+            mutator.description,
+            if mutator.enabled_by_default {
+                ""
+            } else {
+                " (disabled by default)"
+            },
+            id_width = id_width.unwrap_or(0),
+            kind_width = kind_width.unwrap_or(0),
+        ));
+    }
+    list
+}
+
+pub(crate) fn find_mutations(
+    candidates: &[CandidateFunction],
+    bodies: &[FuncBody],
+    mutators: &[StaticMutator],
+) -> Result<Vec<Mutation>, Error> {
     let mut mutations = Vec::<Mutation>::new();
-    for index in indices.iter() {
-        let body = &bodies[*index];
-        for mutator in all_mutators::ALL_MUTATORS.iter() {
-            mutations.append(&mut mutator.find(body, *index));
+    for candidate in candidates.iter() {
+        let body = bodies
+            .get(candidate.body_index)
+            .ok_or(Error::InvalidFunctionIndex(
+                candidate.function_index as usize,
+            ))?;
+        for mutator in mutators.iter() {
+            mutations.extend(
+                mutator
+                    .find(body, candidate.body_index)
+                    .into_iter()
+                    .map(|mutation| {
+                        mutation.in_function(candidate.function_index, &candidate.name)
+                    }),
+            );
         }
     }
-    mutations
+    Ok(mutations)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::candidates::{identify_candidate_functions, FunctionNames};
+    use crate::mutation::{enabled_mutators, find_mutations, Replacement};
+    use crate::test_support::module_with_imported_functions;
+    use parity_wasm::deserialize_buffer;
+    use parity_wasm::elements::{Instruction, Module};
+
+    #[test]
+    fn mutates_body_of_candidate_function_when_module_imports_functions() {
+        let contents = module_with_imported_functions(
+            &["env::print"],
+            &[
+                (
+                    "imports::untouched",
+                    vec![Instruction::Nop, Instruction::End],
+                ),
+                (
+                    "imports::mutated",
+                    vec![Instruction::BrIf(0), Instruction::End],
+                ),
+            ],
+        );
+        let mut module = deserialize_buffer::<Module>(&contents)
+            .unwrap()
+            .parse_names()
+            .unwrap();
+        let names = FunctionNames::read(&module, &contents).unwrap();
+        let candidates =
+            identify_candidate_functions(&module, &names, &["imports::mutated"], &[]).unwrap();
+        let bodies = module.code_section().unwrap().bodies().to_vec();
+
+        let mutations = find_mutations(
+            &candidates,
+            &bodies,
+            &enabled_mutators(&[String::from("IfConditionToTrue")], &[]),
+        )
+        .unwrap();
+
+        assert_eq!(mutations.len(), 1);
+        assert_eq!(mutations[0].function_index(), 3);
+        assert_eq!(mutations[0].function_name(), "imports::mutated");
+        mutations[0].perform(&mut module).unwrap();
+        let bodies = module.code_section().unwrap().bodies();
+        assert_eq!(
+            bodies[1].code().elements(),
+            &[Instruction::Nop, Instruction::End]
+        );
+        assert_eq!(
+            bodies[2].code().elements(),
+            &[Instruction::Drop, Instruction::Br(0), Instruction::End]
+        );
+    }
+
+    #[test]
+    fn reports_instructions_replaced_by_mutation() {
+        let contents = module_with_imported_functions(
+            &[],
+            &[(
+                "branch::taken",
+                vec![
+                    Instruction::I32Const(1),
+                    Instruction::BrIf(0),
+                    Instruction::End,
+                ],
+            )],
+        );
+        let module = deserialize_buffer::<Module>(&contents)
+            .unwrap()
+            .parse_names()
+            .unwrap();
+        let names = FunctionNames::read(&module, &contents).unwrap();
+        let candidates = identify_candidate_functions(&module, &names, &["branch"], &[]).unwrap();
+        let bodies = module.code_section().unwrap().bodies();
+        let mutations = find_mutations(
+            &candidates,
+            bodies,
+            &enabled_mutators(&[String::from("IfConditionToTrue")], &[]),
+        )
+        .unwrap();
+
+        let replacement = mutations[0].replacement(&bodies[1]);
+
+        assert_eq!(
+            replacement,
+            Replacement {
+                start: 1,
+                original: vec![Instruction::BrIf(0)],
+                replacement: vec![Instruction::Drop, Instruction::Br(0)],
+            }
+        );
+    }
 }
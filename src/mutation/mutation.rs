@@ -1,30 +1,168 @@
 use crate::mutation::mutator::Mutator;
-use parity_wasm::elements::Module;
-use std::fmt::{Debug, Formatter, Result};
+use crate::{Error, SourceLocation};
+use parity_wasm::elements::{FuncBody, Instruction, Module};
+use std::fmt::{self, Debug, Formatter};
+
+/// Instructions of a function body which a mutation replaces, and those it puts in their place.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Replacement {
+    /// Index in the original body of the first replaced instruction.
+    pub start: usize,
+    pub original: Vec<Instruction>,
+    pub replacement: Vec<Instruction>,
+}
 
 pub struct Mutation {
     pub(crate) mutator: Box<dyn Mutator>,
     pub(crate) instruction_index: usize,
+    pub(crate) body_index: usize,
     pub(crate) function_index: usize,
+    pub(crate) function_name: String,
+    pub(crate) inlined_into: Option<String>,
+    pub(crate) location: Option<SourceLocation>,
+    pub(crate) id: String,
 }
 
 impl Mutation {
-    pub fn perform(&self, module: &mut Module) {
-        self.mutator.perform(
-            &mut module.code_section_mut().unwrap().bodies_mut()[self.function_index],
-            self.instruction_index,
-        );
+    /// Creates a mutation of the function whose body has the given index in the code section. The
+    /// identity of the function is filled in by [`Mutation::in_function`].
+    pub(crate) fn new(
+        mutator: Box<dyn Mutator>,
+        body_index: usize,
+        instruction_index: usize,
+    ) -> Self {
+        Mutation {
+            mutator,
+            instruction_index,
+            body_index,
+            function_index: body_index,
+            function_name: String::new(),
+            inlined_into: None,
+            location: None,
+            id: String::new(),
+        }
+    }
+
+    pub(crate) fn in_function(mut self, function_index: u32, function_name: &str) -> Self {
+        self.function_index = function_index as usize;
+        self.function_name = String::from(function_name);
+        self
+    }
+
+    /// Attributes the mutation to the function which the compiler inlined into the mutated one and
+    /// from which the mutated instruction was compiled.
+    pub(crate) fn attribute_to_inlined_function(&mut self, function_name: String) {
+        self.inlined_into = Some(std::mem::replace(&mut self.function_name, function_name));
+    }
+
+    /// Identifier of the mutant which, unlike its function and instruction indices, does not change
+    /// when the module is rebuilt with changes to other code. It is made of the demangled path of
+    /// the function, the name of the mutator and a hash of the change and the instructions around
+    /// it, e.g. `roman_numerals::to_roman:InstructionSwapping:ec784069`.
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    /// Index of the mutated function in the function index space of the module, which counts
+    /// imported functions first.
+    pub fn function_index(&self) -> usize {
+        self.function_index
+    }
+
+    /// Demangled name of the mutated function, or of the function from which the mutated code was
+    /// inlined.
+    pub fn function_name(&self) -> &str {
+        &self.function_name
+    }
+
+    /// Demangled name of the function into which the mutated code was inlined, if it was. The
+    /// function index and body index then refer to that function.
+    pub fn inlined_into(&self) -> Option<&str> {
+        self.inlined_into.as_deref()
+    }
+
+    /// Position in the source code from which the mutated instruction was compiled, if the
+    /// module contains debugging information.
+    pub fn location(&self) -> Option<&SourceLocation> {
+        self.location.as_ref()
+    }
+
+    /// Index of the body of the mutated function in the code section.
+    pub fn body_index(&self) -> usize {
+        self.body_index
+    }
+
+    /// Index within the mutated function of the instruction which the mutation changes.
+    pub fn instruction_index(&self) -> usize {
+        self.instruction_index
+    }
+
+    /// Name of the kind of mutator which made the mutation.
+    pub fn mutator_name(&self) -> &'static str {
+        self.mutator.name()
+    }
+
+    /// Returns the instructions of the given original body of the mutated function which the
+    /// mutation changes, and what it changes them to.
+    pub fn replacement(&self, body: &FuncBody) -> Replacement {
+        let mut mutated_body = body.clone();
+        self.mutator
+            .perform(&mut mutated_body, self.instruction_index);
+        let original = body.code().elements();
+        let mutated = mutated_body.code().elements();
+        let prefix = original
+            .iter()
+            .zip(mutated)
+            .take_while(|(original, mutated)| original == mutated)
+            .count();
+        let suffix = original[prefix..]
+            .iter()
+            .rev()
+            .zip(mutated[prefix..].iter().rev())
+            .take_while(|(original, mutated)| original == mutated)
+            .count();
+        Replacement {
+            start: prefix,
+            original: original[prefix..original.len() - suffix].to_vec(),
+            replacement: mutated[prefix..mutated.len() - suffix].to_vec(),
+        }
+    }
+
+    /// Human-readable description of the change made by the mutation.
+    pub fn describe(&self) -> String {
+        self.mutator.describe()
+    }
+
+    pub fn perform(&self, module: &mut Module) -> Result<(), Error> {
+        let body = module
+            .code_section_mut()
+            .ok_or(Error::MissingCodeSection)?
+            .bodies_mut()
+            .get_mut(self.body_index)
+            .ok_or(Error::InvalidFunctionIndex(self.function_index))?;
+        self.mutator.perform(body, self.instruction_index);
+        Ok(())
     }
 }
 
 impl Debug for Mutation {
-    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
         write!(
             f,
-            "\nMutation<{}, {}, {}>",
+            "\nMutation<{}, {}",
             self.mutator.describe(),
-            self.function_index,
-            self.instruction_index
-        )
+            self.function_name
+        )?;
+        if let Some(inlined_into) = &self.inlined_into {
+            write!(f, " inlined into {}", inlined_into)?;
+        }
+        write!(f, " ({}), {}>", self.function_index, self.instruction_index)?;
+        if let Some(location) = &self.location {
+            write!(f, " at {}", location)?;
+        }
+        if !self.id.is_empty() {
+            write!(f, " [{}]", self.id)?;
+        }
+        Ok(())
     }
 }
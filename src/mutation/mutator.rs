@@ -1,10 +1,13 @@
 use crate::mutation::Mutation;
 use parity_wasm::elements::FuncBody;
 
-pub(crate) trait Mutator {
+pub(crate) trait Mutator: Send + Sync {
     fn perform(&self, body: &mut FuncBody, index: usize);
 
-    fn find(&self, body: &FuncBody, function_index: usize) -> Vec<Mutation>;
+    fn find(&self, body: &FuncBody, body_index: usize) -> Vec<Mutation>;
 
     fn describe(&self) -> String;
+
+    /// Name shared by all mutators of this kind, by which they can be enabled.
+    fn name(&self) -> &'static str;
 }
@@ -0,0 +1,254 @@
+//! The mutators which can be applied, grouped under IDs by which they are enabled or disabled.
+
+use crate::mutation::if_condition_to_false_mutator::IfConditionToFalseMutator;
+use crate::mutation::if_condition_to_true_mutator::IfConditionToTrueMutator;
+use crate::mutation::instruction_swapping_mutator::InstructionSwappingMutator;
+use crate::mutation::set_cancelling_mutator::SetCancellingMutator;
+use crate::mutation::StaticMutator;
+use parity_wasm::elements::Instruction::{self, *};
+
+/// A mutator, or a family of instruction swaps, which can be enabled or disabled by its ID.
+pub struct RegisteredMutator {
+    /// Identifier by which the mutator is enabled or disabled, e.g. `swap-i32-comparison`.
+    pub id: &'static str,
+    pub description: &'static str,
+    /// Whether the mutator is applied when no mutators are enabled explicitly. Mutators prone to
+    /// equivalent mutants are only applied when enabled.
+    pub enabled_by_default: bool,
+    mutators: &'static [StaticMutator],
+}
+
+impl RegisteredMutator {
+    /// Name of the kind of the mutators, which is part of the IDs of their mutants and by which
+    /// all mutators of the kind can be enabled or disabled at once.
+    pub fn kind(&self) -> &'static str {
+        self.mutators[0].name()
+    }
+
+    pub(crate) fn mutators(&self) -> &'static [StaticMutator] {
+        self.mutators
+    }
+
+    /// Whether the given name is the ID or the kind of the mutator.
+    pub(crate) fn is_named(&self, name: &str) -> bool {
+        name == self.id || name == self.kind()
+    }
+}
+
+const fn swap(
+    original_instruction: Instruction,
+    replacement_instruction: Instruction,
+) -> InstructionSwappingMutator {
+    InstructionSwappingMutator {
+        original_instruction,
+        replacement_instruction,
+    }
+}
+
+pub(crate) static MUTATORS: [RegisteredMutator; 15] = [
+    RegisteredMutator {
+        id: "swap-i32-comparison",
+        description: "Swaps strict and non-strict i32 comparisons, and equality with inequality",
+        enabled_by_default: true,
+        mutators: &[
+            &swap(I32GeU, I32GtU),
+            &swap(I32LeU, I32LtU),
+            &swap(I32GtU, I32GeU),
+            &swap(I32LtU, I32LeU),
+            &swap(I32GeS, I32GtS),
+            &swap(I32LeS, I32LtS),
+            &swap(I32GtS, I32GeS),
+            &swap(I32LtS, I32LeS),
+            &swap(I32Eq, I32Ne),
+            &swap(I32Ne, I32Eq),
+        ],
+    },
+    RegisteredMutator {
+        id: "swap-i32-arithmetic",
+        description: "Swaps i32 addition with subtraction or multiplication",
+        enabled_by_default: true,
+        mutators: &[
+            &swap(I32Add, I32Sub),
+            &swap(I32Sub, I32Add),
+            &swap(I32Add, I32Mul),
+            &swap(I32Mul, I32Add),
+        ],
+    },
+    RegisteredMutator {
+        id: "swap-i32-bitwise",
+        description: "Swaps i32 bitwise and with bitwise or",
+        enabled_by_default: true,
+        mutators: &[&swap(I32And, I32Or), &swap(I32Or, I32And)],
+    },
+    RegisteredMutator {
+        id: "swap-i64-comparison",
+        description: "Swaps strict and non-strict i64 comparisons, and equality with inequality",
+        enabled_by_default: true,
+        mutators: &[
+            &swap(I64GeU, I64GtU),
+            &swap(I64LeU, I64LtU),
+            &swap(I64GtU, I64GeU),
+            &swap(I64LtU, I64LeU),
+            &swap(I64GeS, I64GtS),
+            &swap(I64LeS, I64LtS),
+            &swap(I64GtS, I64GeS),
+            &swap(I64LtS, I64LeS),
+            &swap(I64Eq, I64Ne),
+            &swap(I64Ne, I64Eq),
+        ],
+    },
+    RegisteredMutator {
+        id: "swap-i64-arithmetic",
+        description: "Swaps i64 addition with subtraction or multiplication",
+        enabled_by_default: true,
+        mutators: &[
+            &swap(I64Add, I64Sub),
+            &swap(I64Sub, I64Add),
+            &swap(I64Add, I64Mul),
+            &swap(I64Mul, I64Add),
+        ],
+    },
+    RegisteredMutator {
+        id: "swap-i64-bitwise",
+        description: "Swaps i64 bitwise and with bitwise or",
+        enabled_by_default: true,
+        mutators: &[&swap(I64And, I64Or), &swap(I64Or, I64And)],
+    },
+    RegisteredMutator {
+        id: "swap-f32-comparison",
+        description: "Swaps strict and non-strict f32 comparisons, and equality with inequality",
+        enabled_by_default: true,
+        mutators: &[
+            &swap(F32Ge, F32Gt),
+            &swap(F32Gt, F32Ge),
+            &swap(F32Eq, F32Ne),
+            &swap(F32Ne, F32Eq),
+        ],
+    },
+    RegisteredMutator {
+        id: "swap-f32-arithmetic",
+        description: "Swaps f32 addition with subtraction or multiplication",
+        enabled_by_default: true,
+        mutators: &[
+            &swap(F32Add, F32Sub),
+            &swap(F32Sub, F32Add),
+            &swap(F32Add, F32Mul),
+            &swap(F32Mul, F32Add),
+        ],
+    },
+    RegisteredMutator {
+        id: "swap-f32-rounding",
+        description: "Swaps rounding f32 values up with rounding them down",
+        enabled_by_default: true,
+        mutators: &[&swap(F32Ceil, F32Floor), &swap(F32Floor, F32Ceil)],
+    },
+    RegisteredMutator {
+        id: "swap-f64-comparison",
+        description: "Swaps strict and non-strict f64 comparisons, and equality with inequality",
+        enabled_by_default: true,
+        mutators: &[
+            &swap(F64Ge, F64Gt),
+            &swap(F64Gt, F64Ge),
+            &swap(F64Eq, F64Ne),
+            &swap(F64Ne, F64Eq),
+        ],
+    },
+    RegisteredMutator {
+        id: "swap-f64-arithmetic",
+        description: "Swaps f64 addition with subtraction or multiplication",
+        enabled_by_default: true,
+        mutators: &[
+            &swap(F64Add, F64Sub),
+            &swap(F64Sub, F64Add),
+            &swap(F64Add, F64Mul),
+            &swap(F64Mul, F64Add),
+        ],
+    },
+    RegisteredMutator {
+        id: "swap-f64-rounding",
+        description: "Swaps rounding f64 values up with rounding them down",
+        enabled_by_default: true,
+        mutators: &[&swap(F64Ceil, F64Floor), &swap(F64Floor, F64Ceil)],
+    },
+    RegisteredMutator {
+        id: "set-cancelling",
+        description: "Drops the values which local.set, global.set and stores would store",
+        enabled_by_default: true,
+        mutators: &[&SetCancellingMutator],
+    },
+    RegisteredMutator {
+        id: "if-condition-to-true",
+        description: "Makes conditional branches always taken",
+        enabled_by_default: true,
+        mutators: &[&IfConditionToTrueMutator],
+    },
+    RegisteredMutator {
+        id: "if-condition-to-false",
+        description: "Makes conditional branches never taken",
+        enabled_by_default: false,
+        mutators: &[&IfConditionToFalseMutator],
+    },
+];
+
+#[cfg(test)]
+mod tests {
+    use crate::mutation::registry::MUTATORS;
+    use crate::mutation::{enabled_mutators, list_mutators, mutator_name, mutator_names};
+    use crate::Error;
+
+    #[test]
+    fn identifies_each_mutator_uniquely() {
+        let names = mutator_names();
+
+        for (index, name) in names.iter().enumerate() {
+            assert!(!names[..index].contains(name), "{} is not unique", name);
+        }
+        for registered in &MUTATORS {
+            assert!(registered
+                .mutators()
+                .iter()
+                .all(|mutator| mutator.name() == registered.kind()));
+        }
+    }
+
+    #[test]
+    fn enables_mutators_by_default_unless_prone_to_equivalent_mutants() {
+        let defaults = enabled_mutators(&[], &[]);
+        let all = enabled_mutators(&[String::from("InstructionSwapping")], &[]).len()
+            + enabled_mutators(&[String::from("set-cancelling")], &[]).len()
+            + enabled_mutators(&[String::from("IfConditionToTrue")], &[]).len();
+
+        assert_eq!(defaults.len(), all);
+        assert!(defaults
+            .iter()
+            .all(|mutator| mutator.name() != "IfConditionToFalse"));
+        assert_eq!(
+            enabled_mutators(&[], &[String::from("swap-f64-rounding")]).len(),
+            defaults.len() - 2
+        );
+    }
+
+    #[test]
+    fn lists_mutators_with_their_kinds() {
+        let list = list_mutators();
+
+        assert_eq!(list.lines().count(), MUTATORS.len());
+        assert!(list
+            .lines()
+            .any(|line| line.starts_with("swap-i32-comparison")
+                && line.contains("InstructionSwapping")));
+        assert!(list
+            .lines()
+            .any(|line| line.starts_with("if-condition-to-false")
+                && line.ends_with("(disabled by default)")));
+    }
+
+    #[test]
+    fn rejects_unknown_mutator_names() {
+        assert_eq!(mutator_name("set-cancelling").unwrap(), "set-cancelling");
+        assert!(matches!(
+            mutator_name("swap-everything"),
+            Err(Error::UnknownMutator(name)) if name == "swap-everything"
+        ));
+    }
+}
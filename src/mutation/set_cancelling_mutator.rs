@@ -24,7 +24,7 @@ impl Mutator for SetCancellingMutator {
         }
     }
 
-    fn find(&self, body: &FuncBody, function_index: usize) -> Vec<Mutation> {
+    fn find(&self, body: &FuncBody, body_index: usize) -> Vec<Mutation> {
         let mut result = Vec::<Mutation>::new();
         for (instruction_index, instruction) in body.code().elements().iter().enumerate() {
             match *instruction {
@@ -34,7 +34,7 @@ impl Mutator for SetCancellingMutator {
                 | Instruction::I64Store(_, _)
                 | Instruction::F32Store(_, _)
                 | Instruction::F64Store(_, _) => {
-                    result.push(self.create_mutation(function_index, instruction_index))
+                    result.push(self.create_mutation(body_index, instruction_index))
                 }
                 _ => {}
             }
@@ -45,15 +45,15 @@ impl Mutator for SetCancellingMutator {
     fn describe(&self) -> String {
         String::from("SetCancelling")
     }
+
+    fn name(&self) -> &'static str {
+        "SetCancelling"
+    }
 }
 
 impl SetCancellingMutator {
-    fn create_mutation(&self, function_index: usize, instruction_index: usize) -> Mutation {
-        Mutation {
-            mutator: Box::new(self.clone()),
-            instruction_index,
-            function_index,
-        }
+    fn create_mutation(&self, body_index: usize, instruction_index: usize) -> Mutation {
+        Mutation::new(Box::new(self.clone()), body_index, instruction_index)
     }
 }
 
@@ -165,7 +165,7 @@ mod tests {
 
                     let result = subject.find(&module.code_section().unwrap().bodies()[0], 0);
 
-                    result[0].perform(&mut module);
+                    result[0].perform(&mut module).unwrap();
                     assert_eq!(
                         module.code_section().unwrap().bodies()[0].code().elements(),
                         $replacement
@@ -202,7 +202,7 @@ mod tests {
 
         let result = subject.find(&module.code_section().unwrap().bodies()[0], 0);
 
-        result[0].perform(&mut module);
+        result[0].perform(&mut module).unwrap();
         assert_eq!(
             module.code_section().unwrap().bodies()[0].code().elements(),
             vec![Instruction::I32Add, Instruction::Drop]
@@ -225,7 +225,7 @@ mod tests {
 
         let result = subject.find(&module.code_section().unwrap().bodies()[1], 1);
 
-        result[0].perform(&mut module);
+        result[0].perform(&mut module).unwrap();
         assert_eq!(
             module.code_section().unwrap().bodies()[1].code().elements(),
             vec![Instruction::Drop]
@@ -250,7 +250,7 @@ mod tests {
         let result = subject.find(&module.code_section().unwrap().bodies()[0], 0);
 
         for mutation in result {
-            mutation.perform(&mut module);
+            mutation.perform(&mut module).unwrap();
         }
         assert_eq!(
             module.code_section().unwrap().bodies()[0].code().elements(),
@@ -0,0 +1,284 @@
+use crate::debug_info::{SourceFiles, SourceLocator};
+use crate::engine::{load_module, MutantOutcome, MutantResult, MutationTestResults, Totals};
+use crate::report::mutant_diff::mutant_diff;
+use crate::report::wat::{wat_lines, ModuleTypes};
+use crate::Error;
+use std::collections::BTreeMap;
+use std::fmt::Write;
+
+const STYLE: &str = "
+body { font-family: sans-serif; margin: 2em; color: #222; }
+table { border-collapse: collapse; }
+th, td { padding: 0.2em 0.8em; text-align: right; border-bottom: 1px solid #ddd; }
+th:first-child, td:first-child { text-align: left; }
+pre { background: #f8f8f8; padding: 1em; line-height: 1.4; overflow-x: auto; }
+.index { display: inline-block; width: 4em; color: #999; }
+.source { color: #777; font-style: italic; }
+.mutant { position: relative; padding: 0 0.2em; border-radius: 3px; cursor: help; }
+.mutant .popover { display: none; position: absolute; left: 0; top: 1.4em; z-index: 1;
+  white-space: pre; background: #333; color: #fff; padding: 0.4em 0.6em; border-radius: 3px; }
+.mutant:hover .popover { display: block; }
+.killed, .timeout, .trapped { background: #c8f0c8; }
+.survived { background: #f8c0c0; }
+.not_covered { background: #f8e8a0; }
+.accepted { background: #e0d8f0; }
+.unviable { background: #ddd; }
+.removed { color: #a00; }
+.added { color: #070; }
+";
+
+/// Outcomes in the order in which they take precedence when colouring an instruction with several
+/// mutants, the least detected first.
+const OUTCOME_PRECEDENCE: [&str; 7] = [
+    "survived",
+    "not_covered",
+    "accepted",
+    "timeout",
+    "trapped",
+    "killed",
+    "unviable",
+];
+
+/// The mutants of one function body.
+struct MutatedFunction<'a> {
+    name: &'a str,
+    body_index: usize,
+    mutants: Vec<&'a MutantResult>,
+}
+
+/// Renders the results as a self-contained HTML page, listing the mutated functions with their
+/// mutation scores and showing the code of each in the WebAssembly text format, with every
+/// mutated instruction coloured by the outcome of its mutants. If the module contains a line
+/// table, the code is interleaved with the source lines it was compiled from, where the source
+/// files can be read. The results must come from a run over the module with the given contents.
+pub fn html_report(results: &MutationTestResults, module_contents: &[u8]) -> Result<String, Error> {
+    let module = load_module(module_contents)?;
+    let bodies = module
+        .code_section()
+        .map_or(&[][..], |section| section.bodies());
+    let types = ModuleTypes::new(&module);
+    let locator = SourceLocator::new(&module, module_contents);
+    let mut sources = SourceFiles::default();
+
+    let mut functions = BTreeMap::<usize, MutatedFunction>::new();
+    for result in &results.mutants {
+        let mutation = &result.mutation;
+        let function = functions
+            .entry(mutation.function_index())
+            .or_insert_with(|| MutatedFunction {
+                name: mutation
+                    .inlined_into()
+                    .unwrap_or_else(|| mutation.function_name()),
+                body_index: mutation.body_index(),
+                mutants: Vec::new(),
+            });
+        function.mutants.push(result);
+    }
+    let totals_by_function = results.totals_by_function();
+
+    let mut html = String::new();
+    html.push_str("<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n");
+    html.push_str("<title>Mutation testing report</title>\n");
+    let _ = writeln!(html, "<style>{}</style>\n</head>\n<body>", STYLE);
+    html.push_str("<h1>Mutation testing report</h1>\n");
+    let _ = writeln!(html, "<p>{}</p>", escape(&results.totals().to_string()));
+
+    html.push_str("<table>\n<tr><th>Function</th><th>Mutants</th><th>Detected</th>");
+    html.push_str("<th>Survived</th><th>Not covered</th><th>Score</th></tr>\n");
+    for (function_index, function) in &functions {
+        let totals = totals_by_function[function_index];
+        let _ = writeln!(
+            html,
+            "<tr><td><a href=\"#function-{}\">{}</a></td><td>{}</td><td>{}</td><td>{}</td>\
+             <td>{}</td><td>{}</td></tr>",
+            function_index,
+            escape(function.name),
+            totals.total(),
+            totals.detected(),
+            totals.survived,
+            totals.not_covered,
+            score(&totals)
+        );
+    }
+    html.push_str("</table>\n");
+
+    for (function_index, function) in &functions {
+        let _ = writeln!(
+            html,
+            "<section id=\"function-{}\">\n<h2>{}</h2>\n<p>{}</p>\n<pre>",
+            function_index,
+            escape(function.name),
+            escape(&totals_by_function[function_index].to_string())
+        );
+        let instructions = match bodies.get(function.body_index) {
+            Some(body) => body.code().elements(),
+            None => &[],
+        };
+        let mut previous_line = None;
+        for (index, line) in wat_lines(instructions).iter().enumerate() {
+            if let Some(location) = locator
+                .as_ref()
+                .and_then(|locator| locator.locate(function.body_index, index))
+            {
+                let source_line = (location.file.clone(), location.line);
+                if previous_line.as_ref() != Some(&source_line) {
+                    let text = sources.line(&location).unwrap_or_default().trim();
+                    let _ = writeln!(
+                        html,
+                        "<span class=\"source\">{}:{}  {}</span>",
+                        escape(&location.file.display().to_string()),
+                        location.line,
+                        escape(text)
+                    );
+                    previous_line = Some(source_line);
+                }
+            }
+            let mutants: Vec<&MutantResult> = function
+                .mutants
+                .iter()
+                .copied()
+                .filter(|result| result.mutation.instruction_index() == index)
+                .collect();
+            let _ = write!(
+                html,
+                "<span class=\"index\">{}</span>{}",
+                index,
+                "  ".repeat(line.depth)
+            );
+            if mutants.is_empty() {
+                let _ = writeln!(html, "{}", escape(&line.text));
+            } else {
+                let _ = writeln!(
+                    html,
+                    "<span class=\"mutant {}\">{}<span class=\"popover\">{}</span></span>",
+                    outcome_class(&mutants),
+                    escape(&line.text),
+                    escape(&popover(&mutants))
+                );
+            }
+        }
+        html.push_str("</pre>\n");
+        let diffs: Vec<String> = function
+            .mutants
+            .iter()
+            .filter(|result| result.outcome == MutantOutcome::Survived)
+            .filter_map(|result| mutant_diff(&result.mutation, &module, &types))
+            .collect();
+        if !diffs.is_empty() {
+            html.push_str("<h3>Surviving mutants</h3>\n");
+        }
+        for diff in diffs {
+            html.push_str("<pre>");
+            for line in diff.lines() {
+                let class = match line.chars().next() {
+                    Some('-') if !line.starts_with("---") => Some("removed"),
+                    Some('+') if !line.starts_with("+++") => Some("added"),
+                    _ => None,
+                };
+                let _ = match class {
+                    Some(class) => {
+                        writeln!(html, "<span class=\"{}\">{}</span>", class, escape(line))
+                    }
+                    None => writeln!(html, "{}", escape(line)),
+                };
+            }
+            html.push_str("</pre>\n");
+        }
+        html.push_str("</section>\n");
+    }
+    html.push_str("</body>\n</html>\n");
+    Ok(html)
+}
+
+/// Returns the class of the least detected outcome among the mutants.
+fn outcome_class(mutants: &[&MutantResult]) -> &'static str {
+    OUTCOME_PRECEDENCE
+        .iter()
+        .copied()
+        .find(|&class| mutants.iter().any(|result| result.outcome.name() == class))
+        .unwrap_or("unviable")
+}
+
+fn popover(mutants: &[&MutantResult]) -> String {
+    mutants
+        .iter()
+        .map(|result| {
+            let mutation = &result.mutation;
+            let mut text = format!("{}: {}", mutation.describe(), describe(result.outcome));
+            if mutation.inlined_into().is_some() {
+                text.push_str(&format!(" (in {}, inlined)", mutation.function_name()));
+            }
+            text.push_str(&format!(" [{}]", mutation.id()));
+            text
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn describe(outcome: MutantOutcome) -> String {
+    match outcome.kill_reason() {
+        Some(reason) => format!("killed, {}", reason),
+        None => match outcome {
+            MutantOutcome::Survived => String::from("survived"),
+            MutantOutcome::Accepted => String::from("survived, accepted"),
+            MutantOutcome::Unviable => String::from("unviable"),
+            _ => String::from("not covered by the tests"),
+        },
+    }
+}
+
+fn score(totals: &Totals) -> String {
+    totals
+        .mutation_score()
+        .map_or_else(|| String::from("-"), |score| format!("{:.1}%", score))
+}
+
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::report::html::escape;
+    use crate::report::html_report;
+    use crate::test_support::{module_with_functions, surviving_function};
+    use crate::MutationTestRun;
+
+    #[test]
+    fn shows_code_of_mutated_functions_with_mutants_coloured_by_outcome() {
+        let module_contents = module_with_functions(&[surviving_function()]);
+        let results = MutationTestRun::new(&module_contents)
+            .include_module("survivor")
+            .run()
+            .unwrap();
+
+        let html = html_report(&results, &module_contents).unwrap();
+
+        assert!(html.contains("<a href=\"#function-1\">survivor::compute</a>"));
+        assert!(html.contains("<span class=\"index\">1</span>i32.const 1"));
+        assert!(html.contains(
+            "<span class=\"mutant survived\">i32.gt_u<span class=\"popover\">\
+             InstructionSwapping&lt;i32.gt_u, i32.ge_u&gt;: survived"
+        ));
+        assert!(html.contains("<span class=\"added\">+     2  i32.ge_u"));
+    }
+
+    #[test]
+    fn escapes_markup() {
+        assert_eq!(
+            escape("<Vec<T> as Debug>::fmt & \"more\""),
+            "&lt;Vec&lt;T&gt; as Debug&gt;::fmt &amp; &quot;more&quot;"
+        );
+    }
+}
@@ -0,0 +1,198 @@
+use crate::candidates::NamingSource;
+use crate::engine::{load_module, MutantOutcome, MutationTestResults};
+use crate::mutation::Replacement;
+use crate::report::mutant_diff::mutant_diff;
+use crate::report::wat::ModuleTypes;
+use crate::Error;
+use serde::Serialize;
+use std::time::Duration;
+
+#[derive(Serialize)]
+struct Report<'a> {
+    summary: Summary,
+    mutants: Vec<Mutant<'a>>,
+    /// IDs of accepted mutants which no longer exist.
+    missing_accepted: Vec<&'a str>,
+}
+
+#[derive(Serialize)]
+struct Summary {
+    total: usize,
+    killed: usize,
+    survived: usize,
+    accepted: usize,
+    timeout: usize,
+    trapped: usize,
+    unviable: usize,
+    not_covered: usize,
+    /// Percentage of viable mutants detected, or `null` if there are none.
+    mutation_score: Option<f64>,
+    baseline_duration_ms: f64,
+    baseline_fuel_consumed: u64,
+    naming_source: &'static str,
+}
+
+#[derive(Serialize)]
+struct Mutant<'a> {
+    id: &'a str,
+    function: &'a str,
+    inlined_into: Option<&'a str>,
+    function_index: usize,
+    mutator: &'static str,
+    description: String,
+    original: Vec<String>,
+    replacement: Vec<String>,
+    instruction_index: usize,
+    location: Option<Location>,
+    outcome: &'static str,
+    kill_reason: Option<String>,
+    duration_ms: f64,
+    /// Diff of the text format of the function for surviving mutants.
+    diff: Option<String>,
+}
+
+#[derive(Serialize)]
+struct Location {
+    file: String,
+    line: u64,
+    column: u64,
+}
+
+/// Renders the results as a JSON document with a summary of the run and the details of each
+/// mutant. The results must come from a run over the module with the given contents, whose code
+/// is needed to show the instructions changed by each mutant.
+///
+/// Each mutant has the ID described at [`crate::mutation::Mutation::id`], which does not change
+/// when code is added to or removed from other functions.
+pub fn json_report(results: &MutationTestResults, module_contents: &[u8]) -> Result<String, Error> {
+    let module = load_module(module_contents)?;
+    let bodies = module
+        .code_section()
+        .map_or(&[][..], |section| section.bodies());
+    let types = ModuleTypes::new(&module);
+    let totals = results.totals();
+    let mutants = results
+        .mutants
+        .iter()
+        .map(|result| {
+            let mutation = &result.mutation;
+            let replacement = bodies
+                .get(mutation.body_index())
+                .map(|body| mutation.replacement(body))
+                .unwrap_or_default();
+            let Replacement {
+                original,
+                replacement,
+                ..
+            } = replacement;
+            Mutant {
+                id: mutation.id(),
+                function: mutation.function_name(),
+                inlined_into: mutation.inlined_into(),
+                function_index: mutation.function_index(),
+                mutator: mutation.mutator_name(),
+                description: mutation.describe(),
+                original: original.iter().map(ToString::to_string).collect(),
+                replacement: replacement.iter().map(ToString::to_string).collect(),
+                instruction_index: mutation.instruction_index(),
+                location: mutation.location().map(|location| Location {
+                    file: location.file.display().to_string(),
+                    line: location.line,
+                    column: location.column,
+                }),
+                outcome: result.outcome.name(),
+                kill_reason: result.outcome.kill_reason(),
+                duration_ms: milliseconds(result.duration),
+                diff: match result.outcome {
+                    MutantOutcome::Survived => mutant_diff(mutation, &module, &types),
+                    _ => None,
+                },
+            }
+        })
+        .collect();
+    let report = Report {
+        summary: Summary {
+            total: totals.total(),
+            killed: totals.killed,
+            survived: totals.survived,
+            accepted: totals.accepted,
+            timeout: totals.timeout,
+            trapped: totals.trapped,
+            unviable: totals.unviable,
+            not_covered: totals.not_covered,
+            mutation_score: totals.mutation_score(),
+            baseline_duration_ms: milliseconds(results.baseline.duration),
+            baseline_fuel_consumed: results.baseline.fuel_consumed,
+            naming_source: naming_source(results.naming_source),
+        },
+        mutants,
+        missing_accepted: results
+            .missing_accepted
+            .iter()
+            .map(|mutant| mutant.id.as_str())
+            .collect(),
+    };
+    serde_json::to_string_pretty(&report).map_err(|e| Error::Report(e.to_string()))
+}
+
+fn milliseconds(duration: Duration) -> f64 {
+    duration.as_secs_f64() * 1000.0
+}
+
+fn naming_source(source: NamingSource) -> &'static str {
+    match source {
+        NamingSource::NameSection => "name_section",
+        NamingSource::Linking => "linking",
+        NamingSource::Dwarf => "dwarf",
+        NamingSource::Exports => "exports",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::report::json_report;
+    use crate::test_support::module_with_functions;
+    use crate::MutationTestRun;
+    use parity_wasm::elements::{BlockType, Instruction};
+    use serde_json::Value;
+
+    #[test]
+    fn reports_each_mutant_with_summary() {
+        let module_contents = module_with_functions(&[(
+            "checked::branch",
+            vec![
+                Instruction::Block(BlockType::NoResult),
+                Instruction::I32Const(1),
+                Instruction::BrIf(0),
+                Instruction::End,
+                Instruction::End,
+            ],
+        )]);
+        let results = MutationTestRun::new(&module_contents)
+            .include_module("checked")
+            .enable_mutator("IfConditionToTrue")
+            .run()
+            .unwrap();
+
+        let report: Value =
+            serde_json::from_str(&json_report(&results, &module_contents).unwrap()).unwrap();
+
+        assert_eq!(report["summary"]["total"], 1);
+        assert_eq!(report["summary"]["survived"], 1);
+        assert_eq!(report["summary"]["naming_source"], "name_section");
+        let mutant = &report["mutants"][0];
+        assert_eq!(mutant["id"], results.mutants[0].mutation.id());
+        assert_eq!(mutant["function"], "checked::branch");
+        assert_eq!(mutant["mutator"], "IfConditionToTrue");
+        assert_eq!(mutant["original"], serde_json::json!(["br_if 0"]));
+        assert_eq!(mutant["replacement"], serde_json::json!(["drop", "br 0"]));
+        assert_eq!(mutant["instruction_index"], 2);
+        assert_eq!(mutant["location"], Value::Null);
+        assert_eq!(mutant["outcome"], "survived");
+        assert_eq!(mutant["kill_reason"], Value::Null);
+        assert!(mutant["diff"]
+            .as_str()
+            .unwrap()
+            .contains("-     2    br_if 0"));
+    }
+}
@@ -0,0 +1,11 @@
+//! Reports of the results of a run in formats meant for other tools and for browsing, beside the
+//! summary printed by the command line tools.
+
+mod html;
+mod json;
+mod mutant_diff;
+mod wat;
+
+pub use html::html_report;
+pub use json::json_report;
+pub use mutant_diff::surviving_mutant_diffs;
@@ -0,0 +1,196 @@
+use crate::engine::{load_module, MutantOutcome, MutationTestResults};
+use crate::mutation::Mutation;
+use crate::report::wat::{wat_lines, ModuleTypes, WatLine};
+use crate::Error;
+use parity_wasm::elements::{Instruction, Module};
+use std::fmt::Write;
+
+/// Number of unchanged instructions shown before and after those changed by a mutation.
+const CONTEXT: usize = 3;
+
+/// Renders the diff of each surviving mutant against its function, as by [`mutant_diff`]. The
+/// results must come from a run over the module with the given contents.
+pub fn surviving_mutant_diffs<'a>(
+    results: &'a MutationTestResults,
+    module_contents: &[u8],
+) -> Result<Vec<(&'a Mutation, String)>, Error> {
+    let module = load_module(module_contents)?;
+    let types = ModuleTypes::new(&module);
+    Ok(results
+        .mutants
+        .iter()
+        .filter(|result| result.outcome == MutantOutcome::Survived)
+        .filter_map(|result| {
+            let diff = mutant_diff(&result.mutation, &module, &types)?;
+            Some((&result.mutation, diff))
+        })
+        .collect())
+}
+
+/// Renders the instructions changed by the mutation as a diff of the text format of the original
+/// and mutated function, with a few unchanged instructions around them. Each instruction is shown
+/// with its index in its version of the function and, where it does not depend on the enclosing
+/// blocks, its effect on the operand stack. Returns `None` if the module does not define the
+/// mutated function.
+pub(crate) fn mutant_diff(
+    mutation: &Mutation,
+    module: &Module,
+    types: &ModuleTypes,
+) -> Option<String> {
+    let body = module.code_section()?.bodies().get(mutation.body_index())?;
+    let replacement = mutation.replacement(body);
+    let original = body.code().elements();
+    let original_end = replacement.start + replacement.original.len();
+    let mut mutated = original[..replacement.start].to_vec();
+    mutated.extend_from_slice(&replacement.replacement);
+    mutated.extend_from_slice(&original[original_end..]);
+    let mutated_end = replacement.start + replacement.replacement.len();
+    let original_lines = wat_lines(original);
+    let mutated_lines = wat_lines(&mutated);
+    let locals = types.locals(module, mutation.body_index());
+
+    let first = replacement.start.saturating_sub(CONTEXT);
+    let last = (original_end + CONTEXT).min(original.len());
+    let mut rows = Vec::new();
+    let mut row = |marker: char, index: usize, lines: &[WatLine], instructions: &[Instruction]| {
+        let line = &lines[index];
+        rows.push((
+            marker,
+            index,
+            format!("{}{}", "  ".repeat(line.depth), line.text),
+            types
+                .stack_effect(&instructions[index], &locals)
+                .map(|effect| effect.to_string()),
+        ));
+    };
+    for index in first..replacement.start {
+        row(' ', index, &original_lines, original);
+    }
+    for index in replacement.start..original_end {
+        row('-', index, &original_lines, original);
+    }
+    for index in replacement.start..mutated_end {
+        row('+', index, &mutated_lines, &mutated);
+    }
+    for index in original_end..last {
+        row(' ', index, &original_lines, original);
+    }
+
+    // The body is that of the function into which the mutated code was inlined, if it was.
+    let function_name = mutation
+        .inlined_into()
+        .unwrap_or_else(|| mutation.function_name());
+    let description = match mutation.inlined_into() {
+        Some(_) => format!(
+            "{}, inlined from {}",
+            mutation.describe(),
+            mutation.function_name()
+        ),
+        None => mutation.describe(),
+    };
+    let mut diff = format!(
+        "--- {}\n+++ {} ({})\n@@ -{},{} +{},{} @@\n",
+        function_name,
+        function_name,
+        description,
+        first,
+        last - first,
+        first,
+        last - first + replacement.replacement.len() - replacement.original.len(),
+    );
+    let width = rows
+        .iter()
+        .map(|(_, _, text, _)| text.len())
+        .max()
+        .unwrap_or(0);
+    for (marker, index, text, effect) in rows {
+        let _ = match effect {
+            Some(effect) => writeln!(
+                diff,
+                "{} {:>5}  {:<width$}  ;; {}",
+                marker,
+                index,
+                text,
+                effect,
+                width = width
+            ),
+            None => writeln!(diff, "{} {:>5}  {}", marker, index, text),
+        };
+    }
+    Some(diff)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::report::surviving_mutant_diffs;
+    use crate::test_support::module_with_functions;
+    use crate::MutationTestRun;
+    use parity_wasm::elements::{BlockType, Instruction};
+
+    #[test]
+    fn shows_changed_instructions_with_context_and_stack_effects() {
+        let module_contents = module_with_functions(&[(
+            "checked::branch",
+            vec![
+                Instruction::Block(BlockType::NoResult),
+                Instruction::Nop,
+                Instruction::I32Const(1),
+                Instruction::BrIf(0),
+                Instruction::Nop,
+                Instruction::End,
+                Instruction::End,
+            ],
+        )]);
+        let results = MutationTestRun::new(&module_contents)
+            .include_module("checked")
+            .enable_mutator("IfConditionToTrue")
+            .run()
+            .unwrap();
+
+        let diffs = surviving_mutant_diffs(&results, &module_contents).unwrap();
+
+        assert_eq!(diffs.len(), 1);
+        assert_eq!(
+            diffs[0].1,
+            "--- checked::branch\n\
+             +++ checked::branch (IfConditionToTrue)\n\
+             @@ -0,7 +0,8 @@\n  \
+             \x20   0  block\n  \
+             \x20   1    nop          ;; [] -> []\n  \
+             \x20   2    i32.const 1  ;; [] -> [i32]\n\
+             -     3    br_if 0      ;; [i32] -> []\n\
+             +     3    drop         ;; [any] -> []\n\
+             +     4    br 0\n  \
+             \x20   4    nop          ;; [] -> []\n  \
+             \x20   5  end\n  \
+             \x20   6  end\n"
+        );
+    }
+
+    #[test]
+    fn names_function_containing_inlined_code_in_header() {
+        let module_contents = module_with_functions(&[(
+            "checked::branch",
+            vec![
+                Instruction::I32Const(1),
+                Instruction::BrIf(0),
+                Instruction::End,
+            ],
+        )]);
+        let mut results = MutationTestRun::new(&module_contents)
+            .include_module("checked")
+            .enable_mutator("IfConditionToTrue")
+            .run()
+            .unwrap();
+        results.mutants[0]
+            .mutation
+            .attribute_to_inlined_function(String::from("helpers::is_set"));
+
+        let diffs = surviving_mutant_diffs(&results, &module_contents).unwrap();
+
+        assert!(diffs[0].1.starts_with(
+            "--- checked::branch\n\
+             +++ checked::branch (IfConditionToTrue, inlined from helpers::is_set)\n"
+        ));
+    }
+}
@@ -0,0 +1,314 @@
+use parity_wasm::elements::{
+    BlockType, External, FunctionType, Instruction, Module, Type, ValueType,
+};
+use std::fmt::{self, Display, Formatter};
+
+/// Operations of the form `<type>.<operation>` which compare two values.
+const COMPARISONS: [&str; 14] = [
+    "eq", "ne", "lt", "lt_s", "lt_u", "gt", "gt_s", "gt_u", "le", "le_s", "le_u", "ge", "ge_s",
+    "ge_u",
+];
+
+/// Operations of the form `<type>.<operation>` which take and return a single value of the type.
+const UNARY_OPERATIONS: [&str; 10] = [
+    "clz", "ctz", "popcnt", "abs", "neg", "ceil", "floor", "trunc", "nearest", "sqrt",
+];
+
+/// An instruction of a function body in the WebAssembly text format, indented by the number of
+/// blocks enclosing it.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) struct WatLine {
+    pub(crate) depth: usize,
+    pub(crate) text: String,
+}
+
+/// Returns one line for each of the given instructions, which make up a whole function body.
+pub(crate) fn wat_lines(instructions: &[Instruction]) -> Vec<WatLine> {
+    let mut depth = 0;
+    instructions
+        .iter()
+        .map(|instruction| {
+            if let Instruction::End | Instruction::Else = instruction {
+                depth = usize::saturating_sub(depth, 1);
+            }
+            let line = WatLine {
+                depth,
+                text: wat_instruction(instruction),
+            };
+            if let Instruction::Block(_)
+            | Instruction::Loop(_)
+            | Instruction::If(_)
+            | Instruction::Else = instruction
+            {
+                depth += 1;
+            }
+            line
+        })
+        .collect()
+}
+
+/// Returns the text format of the instruction. Block types are written as in WAT rather than as
+/// `parity_wasm` displays them.
+pub(crate) fn wat_instruction(instruction: &Instruction) -> String {
+    let (keyword, block_type) = match instruction {
+        Instruction::Block(block_type) => ("block", block_type),
+        Instruction::Loop(block_type) => ("loop", block_type),
+        Instruction::If(block_type) => ("if", block_type),
+        _ => return instruction.to_string(),
+    };
+    match block_type {
+        BlockType::NoResult => String::from(keyword),
+        BlockType::Value(value_type) => format!("{} (result {})", keyword, value_type),
+    }
+}
+
+/// Types of the values which an instruction pops from the operand stack and of those it pushes.
+/// `any` stands for a value of whichever type the stack holds.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) struct StackEffect {
+    pops: Vec<String>,
+    pushes: Vec<String>,
+}
+
+impl StackEffect {
+    fn new<P, Q>(pops: P, pushes: Q) -> Self
+    where
+        P: IntoIterator,
+        P::Item: ToString,
+        Q: IntoIterator,
+        Q::Item: ToString,
+    {
+        StackEffect {
+            pops: pops.into_iter().map(|t| t.to_string()).collect(),
+            pushes: pushes.into_iter().map(|t| t.to_string()).collect(),
+        }
+    }
+}
+
+impl Display for StackEffect {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "[{}] -> [{}]",
+            self.pops.join(" "),
+            self.pushes.join(" ")
+        )
+    }
+}
+
+/// Types of the functions and globals of a module, by index, which determine the stack effects of
+/// calls and global accesses.
+pub(crate) struct ModuleTypes {
+    functions: Vec<FunctionType>,
+    globals: Vec<ValueType>,
+    types: Vec<FunctionType>,
+    imported_functions: usize,
+}
+
+impl ModuleTypes {
+    pub(crate) fn new(module: &Module) -> Self {
+        let types: Vec<FunctionType> = module
+            .type_section()
+            .map_or(&[][..], |section| section.types())
+            .iter()
+            .map(|Type::Function(function_type)| function_type.clone())
+            .collect();
+        let function_type = |index: u32| types.get(index as usize).cloned().unwrap_or_default();
+        let imports = module
+            .import_section()
+            .map_or(&[][..], |section| section.entries());
+        let mut functions: Vec<FunctionType> = imports
+            .iter()
+            .filter_map(|import| match import.external() {
+                External::Function(index) => Some(function_type(*index)),
+                _ => None,
+            })
+            .collect();
+        let imported_functions = functions.len();
+        functions.extend(
+            module
+                .function_section()
+                .map_or(&[][..], |section| section.entries())
+                .iter()
+                .map(|function| function_type(function.type_ref())),
+        );
+        let mut globals: Vec<ValueType> = imports
+            .iter()
+            .filter_map(|import| match import.external() {
+                External::Global(global_type) => Some(global_type.content_type()),
+                _ => None,
+            })
+            .collect();
+        globals.extend(
+            module
+                .global_section()
+                .map_or(&[][..], |section| section.entries())
+                .iter()
+                .map(|global| global.global_type().content_type()),
+        );
+        ModuleTypes {
+            functions,
+            globals,
+            types,
+            imported_functions,
+        }
+    }
+
+    /// Returns the types of the parameters and locals of the function with the given body.
+    pub(crate) fn locals(&self, module: &Module, body_index: usize) -> Vec<ValueType> {
+        let mut locals: Vec<ValueType> = self
+            .functions
+            .get(self.imported_functions + body_index)
+            .map_or(&[][..], |function| function.params())
+            .to_vec();
+        let body = module
+            .code_section()
+            .and_then(|section| section.bodies().get(body_index));
+        for local in body.map_or(&[][..], |body| body.locals()) {
+            locals.extend((0..local.count()).map(|_| local.value_type()));
+        }
+        locals
+    }
+
+    /// Returns the stack effect of the instruction within a function with the given locals, or
+    /// `None` for instructions which transfer control or whose effect depends on the enclosing
+    /// blocks.
+    pub(crate) fn stack_effect(
+        &self,
+        instruction: &Instruction,
+        locals: &[ValueType],
+    ) -> Option<StackEffect> {
+        let local = |index: &u32| locals.get(*index as usize).map(ToString::to_string);
+        let global = |index: &u32| self.globals.get(*index as usize).map(ToString::to_string);
+        let none: [String; 0] = [];
+        Some(match instruction {
+            Instruction::Nop => StackEffect::new(none.clone(), none),
+            Instruction::If(_) | Instruction::BrIf(_) => StackEffect::new(["i32"], none),
+            Instruction::Drop => StackEffect::new(["any"], none),
+            Instruction::Select => StackEffect::new(["any", "any", "i32"], ["any"]),
+            Instruction::GetLocal(index) => StackEffect::new(none, [local(index)?]),
+            Instruction::SetLocal(index) => StackEffect::new([local(index)?], none),
+            Instruction::TeeLocal(index) => StackEffect::new([local(index)?], [local(index)?]),
+            Instruction::GetGlobal(index) => StackEffect::new(none, [global(index)?]),
+            Instruction::SetGlobal(index) => StackEffect::new([global(index)?], none),
+            Instruction::Call(index) => call_effect(self.functions.get(*index as usize)?, false),
+            Instruction::CallIndirect(index, _) => {
+                call_effect(self.types.get(*index as usize)?, true)
+            }
+            Instruction::CurrentMemory(_) => StackEffect::new(none, ["i32"]),
+            Instruction::GrowMemory(_) => StackEffect::new(["i32"], ["i32"]),
+            _ => numeric_effect(&instruction.to_string())?,
+        })
+    }
+}
+
+fn call_effect(function_type: &FunctionType, indirect: bool) -> StackEffect {
+    let mut pops: Vec<String> = function_type
+        .params()
+        .iter()
+        .map(ToString::to_string)
+        .collect();
+    if indirect {
+        pops.push(String::from("i32"));
+    }
+    StackEffect::new(pops, function_type.return_type())
+}
+
+/// Returns the stack effect of an instruction of the form `<type>.<operation>` from its text.
+fn numeric_effect(text: &str) -> Option<StackEffect> {
+    let mnemonic = text.split_whitespace().next()?;
+    let (value_type, operation) = mnemonic.split_once('.')?;
+    let effect = if let Some((_, source_type)) = operation.split_once('/') {
+        StackEffect::new([source_type], [value_type])
+    } else if operation == "const" {
+        StackEffect::new(Vec::<&str>::new(), [value_type])
+    } else if operation.starts_with("load") {
+        StackEffect::new(["i32"], [value_type])
+    } else if operation.starts_with("store") {
+        StackEffect::new(["i32", value_type], Vec::<&str>::new())
+    } else if operation == "eqz" {
+        StackEffect::new([value_type], ["i32"])
+    } else if COMPARISONS.contains(&operation) {
+        StackEffect::new([value_type, value_type], ["i32"])
+    } else if UNARY_OPERATIONS.contains(&operation) || operation.starts_with("extend") {
+        StackEffect::new([value_type], [value_type])
+    } else {
+        StackEffect::new([value_type, value_type], [value_type])
+    };
+    Some(effect)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::report::wat::{wat_lines, ModuleTypes, WatLine};
+    use parity_wasm::builder::ModuleBuilder;
+    use parity_wasm::elements::{BlockType, Instruction, ValueType};
+
+    #[test]
+    fn indents_instructions_within_blocks() {
+        let lines = wat_lines(&[
+            Instruction::Block(BlockType::Value(ValueType::I32)),
+            Instruction::I32Const(1),
+            Instruction::If(BlockType::NoResult),
+            Instruction::Nop,
+            Instruction::Else,
+            Instruction::Unreachable,
+            Instruction::End,
+            Instruction::End,
+            Instruction::End,
+        ]);
+
+        let line = |depth, text: &str| WatLine {
+            depth,
+            text: String::from(text),
+        };
+        assert_eq!(
+            lines,
+            vec![
+                line(0, "block (result i32)"),
+                line(1, "i32.const 1"),
+                line(1, "if"),
+                line(2, "nop"),
+                line(1, "else"),
+                line(2, "unreachable"),
+                line(1, "end"),
+                line(0, "end"),
+                line(0, "end"),
+            ]
+        );
+    }
+
+    #[test]
+    fn describes_stack_effects_of_instructions() {
+        let module = ModuleBuilder::new()
+            .function()
+            .signature()
+            .with_params(vec![ValueType::I64])
+            .with_return_type(Some(ValueType::I32))
+            .build()
+            .body()
+            .build()
+            .build()
+            .build();
+        let types = ModuleTypes::new(&module);
+        let locals = types.locals(&module, 0);
+        let effect = |instruction| {
+            types
+                .stack_effect(&instruction, &locals)
+                .map(|effect| effect.to_string())
+        };
+
+        assert_eq!(effect(Instruction::I32Add).unwrap(), "[i32 i32] -> [i32]");
+        assert_eq!(effect(Instruction::I64LtU).unwrap(), "[i64 i64] -> [i32]");
+        assert_eq!(effect(Instruction::I32WrapI64).unwrap(), "[i64] -> [i32]");
+        assert_eq!(effect(Instruction::F64Const(0)).unwrap(), "[] -> [f64]");
+        assert_eq!(
+            effect(Instruction::I32Store(2, 0)).unwrap(),
+            "[i32 i32] -> []"
+        );
+        assert_eq!(effect(Instruction::GetLocal(0)).unwrap(), "[] -> [i64]");
+        assert_eq!(effect(Instruction::Call(0)).unwrap(), "[i64] -> [i32]");
+        assert_eq!(effect(Instruction::BrIf(0)).unwrap(), "[i32] -> []");
+        assert_eq!(effect(Instruction::Br(0)), None);
+    }
+}
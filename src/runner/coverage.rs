@@ -0,0 +1,114 @@
+use crate::runner::fuel::{self, UNLIMITED_FUEL};
+use crate::runner::instrumentation::{
+    add_mutable_global, export_global_getter, prepend_instructions, read_global,
+};
+use crate::runner::{run_metered_instance, RunnerOptions, TestResult};
+use crate::Error;
+use parity_wasm::elements::{Instruction, Module, ValueType};
+use std::collections::HashSet;
+use wasmi::RuntimeValue;
+
+const COVERAGE_EXPORT_PREFIX: &str = "__mutation_coverage_";
+
+/// Result of running the tests in a module instrumented to record coverage.
+#[derive(Debug)]
+pub(crate) struct CoverageRun {
+    pub(crate) result: TestResult,
+    /// Indices in the code section of the bodies of the functions which the tests entered.
+    pub(crate) covered_functions: HashSet<usize>,
+    /// Fuel used by the tests, i.e. approximately the number of instructions they executed.
+    pub(crate) fuel_consumed: u64,
+}
+
+/// Runs the tests in the given module, recording which of the given functions were entered and
+/// how much fuel the tests used.
+///
+/// Each function is instrumented to set a dedicated global when it is entered. The function
+/// indices refer to the bodies of the code section. Fuel is limited only if the options say so.
+pub(crate) fn run_tests_with_coverage(
+    module: &Module,
+    function_indices: &[usize],
+    options: &RunnerOptions,
+) -> Result<CoverageRun, Error> {
+    let mut module = module.clone();
+    let mut flags = Vec::with_capacity(function_indices.len());
+    for &index in function_indices {
+        let flag = add_mutable_global(&mut module, ValueType::I32, Instruction::I32Const(0));
+        prepend_instructions(
+            &mut module,
+            index,
+            &[Instruction::I32Const(1), Instruction::SetGlobal(flag)],
+        );
+        flags.push(flag);
+    }
+    let fuel = options.fuel.unwrap_or(UNLIMITED_FUEL);
+    fuel::meter(&mut module, fuel);
+    for (&index, &flag) in function_indices.iter().zip(flags.iter()) {
+        export_global_getter(
+            &mut module,
+            flag,
+            ValueType::I32,
+            &coverage_export_name(index),
+        );
+    }
+
+    let serialized = module
+        .to_bytes()
+        .map_err(|e| Error::Serialization(e.to_string()))?;
+    let (result, instance) = run_metered_instance(&serialized, options)?;
+    let mut run = CoverageRun {
+        result,
+        covered_functions: HashSet::new(),
+        fuel_consumed: 0,
+    };
+    if let Some(instance) = instance {
+        for &index in function_indices {
+            if read_global(&instance, &coverage_export_name(index)) == Some(RuntimeValue::I32(1)) {
+                run.covered_functions.insert(index);
+            }
+        }
+        if let Some(remaining) = fuel::remaining_fuel(&instance) {
+            run.fuel_consumed = (fuel as i64 - remaining.max(0)) as u64;
+        }
+    }
+    Ok(run)
+}
+
+fn coverage_export_name(function_index: usize) -> String {
+    format!("{}{}", COVERAGE_EXPORT_PREFIX, function_index)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::runner::coverage::run_tests_with_coverage;
+    use crate::runner::tests::test_module;
+    use crate::runner::{RunnerOptions, TestResult};
+    use parity_wasm::deserialize_buffer;
+    use parity_wasm::elements::{Instruction, Module};
+
+    #[test]
+    fn records_functions_entered_by_tests() {
+        let module =
+            deserialize_buffer::<Module>(include_bytes!("res/cases/test-example.wasm")).unwrap();
+        let function_count = module.code_section().unwrap().bodies().len();
+        let all_functions: Vec<usize> = (0..function_count).collect();
+
+        let run =
+            run_tests_with_coverage(&module, &all_functions, &RunnerOptions::default()).unwrap();
+
+        assert_eq!(run.result, TestResult::Passed);
+        assert!(!run.covered_functions.is_empty());
+        assert!(run.covered_functions.len() < function_count);
+        assert!(run.fuel_consumed > 0);
+    }
+
+    #[test]
+    fn records_coverage_in_module_without_globals() {
+        let module = deserialize_buffer::<Module>(&test_module(vec![Instruction::End])).unwrap();
+
+        let run = run_tests_with_coverage(&module, &[0], &RunnerOptions::default()).unwrap();
+
+        assert_eq!(run.result, TestResult::Passed);
+        assert!(run.covered_functions.contains(&0));
+    }
+}
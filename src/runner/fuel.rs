@@ -0,0 +1,172 @@
+use crate::runner::instrumentation::{add_mutable_global, export_global_getter, read_global};
+use parity_wasm::elements::{BlockType, Instruction, Module, ValueType};
+use wasmi::{ModuleRef, RuntimeValue};
+
+const FUEL_EXPORT: &str = "__mutation_fuel";
+
+/// Fuel given to a run which should not be limited.
+pub(crate) const UNLIMITED_FUEL: u64 = i64::MAX as u64;
+
+/// Instruments every function of the module to consume fuel for each instruction it executes and
+/// to trap once the given amount of fuel is used up.
+///
+/// Fuel is charged at the start of each straight-line sequence of instructions for the whole
+/// sequence, so that the check adds little overhead. The remaining fuel can be read with
+/// [`remaining_fuel`] after running the module.
+pub(crate) fn meter(module: &mut Module, fuel: u64) {
+    let fuel = fuel.min(UNLIMITED_FUEL) as i64;
+    let global = add_mutable_global(module, ValueType::I64, Instruction::I64Const(fuel));
+    if let Some(code) = module.code_section_mut() {
+        for body in code.bodies_mut() {
+            let elements = body.code_mut().elements_mut();
+            let metered = meter_instructions(elements, global);
+            *elements = metered;
+        }
+    }
+    export_global_getter(module, global, ValueType::I64, FUEL_EXPORT);
+}
+
+/// Returns the fuel left in an instance of a module instrumented by [`meter`]. This is negative
+/// if the module ran out of fuel.
+pub(crate) fn remaining_fuel(instance: &ModuleRef) -> Option<i64> {
+    match read_global(instance, FUEL_EXPORT) {
+        Some(RuntimeValue::I64(fuel)) => Some(fuel),
+        _ => None,
+    }
+}
+
+fn meter_instructions(instructions: &[Instruction], global: u32) -> Vec<Instruction> {
+    let mut result = Vec::with_capacity(instructions.len() * 2);
+    let mut segment_start = 0;
+    for (index, instruction) in instructions.iter().enumerate() {
+        let is_last = index + 1 == instructions.len();
+        if starts_new_segment(instruction) && !is_last {
+            let segment = &instructions[segment_start..=index];
+            result.extend(charge(segment.len() as i64, global));
+            result.extend_from_slice(segment);
+            segment_start = index + 1;
+        }
+    }
+    let segment = &instructions[segment_start..];
+    if !segment.is_empty() {
+        result.extend(charge(segment.len() as i64, global));
+        result.extend_from_slice(segment);
+    }
+    result
+}
+
+/// Whether the instruction following this one may be reached other than by executing this one,
+/// i.e. is the target of a branch or the start of a block.
+fn starts_new_segment(instruction: &Instruction) -> bool {
+    matches!(
+        instruction,
+        Instruction::Block(_)
+            | Instruction::Loop(_)
+            | Instruction::If(_)
+            | Instruction::Else
+            | Instruction::End
+            | Instruction::BrIf(_)
+    )
+}
+
+fn charge(cost: i64, global: u32) -> Vec<Instruction> {
+    vec![
+        Instruction::GetGlobal(global),
+        Instruction::I64Const(cost),
+        Instruction::I64Sub,
+        Instruction::SetGlobal(global),
+        Instruction::GetGlobal(global),
+        Instruction::I64Const(0),
+        Instruction::I64LtS,
+        Instruction::If(BlockType::NoResult),
+        Instruction::Unreachable,
+        Instruction::End,
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::runner::fuel::meter_instructions;
+    use crate::runner::tests::test_module;
+    use crate::runner::{self, RunnerOptions, TestResult};
+    use parity_wasm::elements::{BlockType, Instruction};
+
+    #[test]
+    fn charges_each_segment_before_it_runs() {
+        let metered = meter_instructions(
+            &[
+                Instruction::Nop,
+                Instruction::Loop(BlockType::NoResult),
+                Instruction::Nop,
+                Instruction::End,
+                Instruction::End,
+            ],
+            0,
+        );
+
+        let charges: Vec<&Instruction> = metered
+            .windows(3)
+            .filter(|window| window[0] == Instruction::GetGlobal(0))
+            .filter(|window| window[2] == Instruction::I64Sub)
+            .map(|window| &window[1])
+            .collect();
+        assert_eq!(
+            charges,
+            vec![
+                &Instruction::I64Const(2),
+                &Instruction::I64Const(2),
+                &Instruction::I64Const(1)
+            ]
+        );
+        assert_eq!(metered.last(), Some(&Instruction::End));
+    }
+
+    #[test]
+    fn reports_timeout_when_fuel_runs_out() {
+        let module = test_module(vec![
+            Instruction::Loop(BlockType::NoResult),
+            Instruction::Br(0),
+            Instruction::End,
+            Instruction::End,
+        ]);
+
+        let result = runner::run_tests_with_options(
+            &module,
+            &RunnerOptions {
+                fuel: Some(1000),
+                ..RunnerOptions::default()
+            },
+        )
+        .unwrap();
+
+        assert_eq!(result, TestResult::Timeout);
+    }
+
+    #[test]
+    fn passes_when_tests_finish_within_fuel() {
+        let result = runner::run_tests_with_options(
+            include_bytes!("res/cases/test-example.wasm"),
+            &RunnerOptions {
+                fuel: Some(100_000_000),
+                ..RunnerOptions::default()
+            },
+        )
+        .unwrap();
+
+        assert_eq!(result, TestResult::Passed);
+    }
+
+    #[test]
+    fn reports_failure_rather_than_timeout_when_fuel_remains() {
+        let result = runner::run_tests_with_options(
+            include_bytes!("res/cases/test-example-failing.wasm"),
+            &RunnerOptions {
+                fuel: Some(100_000_000),
+                ..RunnerOptions::default()
+            },
+        )
+        .unwrap();
+
+        assert_eq!(result, TestResult::Failed);
+    }
+}
@@ -0,0 +1,113 @@
+use parity_wasm::builder;
+use parity_wasm::elements::{
+    CodeSection, ExportSection, Func, FuncBody, FunctionSection, FunctionType, GlobalSection,
+    Instruction, Instructions, Module, Section, Type, TypeSection, ValueType,
+};
+use wasmi::{ExternVal, ModuleRef, NopExternals, RuntimeValue};
+
+/// Adds a mutable global with the given initial value to the module and returns its index.
+pub(crate) fn add_mutable_global(
+    module: &mut Module,
+    value_type: ValueType,
+    init: Instruction,
+) -> u32 {
+    if module.global_section().is_none() {
+        insert_section(module, Section::Global(GlobalSection::default()));
+    }
+    let index = module.globals_space() as u32;
+    let global = builder::global()
+        .with_type(value_type)
+        .mutable()
+        .init_expr(init)
+        .build();
+    module
+        .global_section_mut()
+        .unwrap()
+        .entries_mut()
+        .push(global);
+    index
+}
+
+/// Exports a function without parameters returning the current value of the given global.
+///
+/// The global itself cannot be exported, since the interpreter only permits exporting immutable
+/// globals.
+pub(crate) fn export_global_getter(
+    module: &mut Module,
+    global_index: u32,
+    value_type: ValueType,
+    name: &str,
+) {
+    for section in [
+        Section::Type(TypeSection::default()),
+        Section::Function(FunctionSection::default()),
+        Section::Export(ExportSection::default()),
+        Section::Code(CodeSection::default()),
+    ] {
+        if !has_section(module, &section) {
+            insert_section(module, section);
+        }
+    }
+    let types = module.type_section_mut().unwrap().types_mut();
+    let type_index = types.len() as u32;
+    types.push(Type::Function(FunctionType::new(vec![], Some(value_type))));
+    let function_index = module.functions_space() as u32;
+    module
+        .function_section_mut()
+        .unwrap()
+        .entries_mut()
+        .push(Func::new(type_index));
+    module
+        .code_section_mut()
+        .unwrap()
+        .bodies_mut()
+        .push(FuncBody::new(
+            vec![],
+            Instructions::new(vec![Instruction::GetGlobal(global_index), Instruction::End]),
+        ));
+    module.export_section_mut().unwrap().entries_mut().push(
+        builder::export()
+            .field(name)
+            .internal()
+            .func(function_index)
+            .build(),
+    );
+}
+
+/// Reads a global exported through [`export_global_getter`] from a running instance.
+pub(crate) fn read_global(instance: &ModuleRef, name: &str) -> Option<RuntimeValue> {
+    match instance.export_by_name(name) {
+        Some(ExternVal::Func(_)) => instance
+            .invoke_export(name, &[], &mut NopExternals)
+            .ok()
+            .flatten(),
+        _ => None,
+    }
+}
+
+/// Inserts instructions at the start of the body of the defined function with the given index.
+pub(crate) fn prepend_instructions(
+    module: &mut Module,
+    body_index: usize,
+    instructions: &[Instruction],
+) {
+    if let Some(body) = module
+        .code_section_mut()
+        .and_then(|code| code.bodies_mut().get_mut(body_index))
+    {
+        let elements = body.code_mut().elements_mut();
+        elements.splice(0..0, instructions.iter().cloned());
+    }
+}
+
+fn has_section(module: &Module, section: &Section) -> bool {
+    module
+        .sections()
+        .iter()
+        .any(|existing| std::mem::discriminant(existing) == std::mem::discriminant(section))
+}
+
+fn insert_section(module: &mut Module, section: Section) {
+    // Only sections which are absent are inserted, which cannot fail.
+    module.insert_section(section).unwrap();
+}
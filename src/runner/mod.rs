@@ -1,43 +1,285 @@
-use wasmi::{ImportsBuilder, NopExternals, RuntimeValue};
+mod coverage;
+mod fuel;
+mod instrumentation;
 
-pub fn run_tests(wasm_bytes: &[u8]) -> TestResult {
-    let module = wasmi::Module::from_buffer(wasm_bytes).unwrap();
-    let instance = wasmi::ModuleInstance::new(&module, &ImportsBuilder::default())
-        .unwrap()
-        .assert_no_start();
+use crate::Error;
+pub(crate) use coverage::run_tests_with_coverage;
+use parity_wasm::deserialize_buffer;
+use parity_wasm::elements::Module;
+use wasmi::{
+    ExternVal, ImportsBuilder, ModuleInstance, ModuleRef, NopExternals, RuntimeValue, ValueType,
+};
+
+/// Options controlling how the tests in a module are run.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RunnerOptions {
+    /// Name of the exported function which runs the tests. It is called with two zero arguments,
+    /// like the `main` function of a test binary.
+    pub entry_point: String,
+    /// Number of instructions the tests may execute before they are stopped and reported as
+    /// having timed out. Unlimited if `None`.
+    pub fuel: Option<u64>,
+}
+
+impl Default for RunnerOptions {
+    fn default() -> Self {
+        RunnerOptions {
+            entry_point: String::from("main"),
+            fuel: None,
+        }
+    }
+}
+
+pub fn run_tests(wasm_bytes: &[u8]) -> Result<TestResult, Error> {
+    run_tests_with_options(wasm_bytes, &RunnerOptions::default())
+}
+
+pub fn run_tests_with_options(
+    wasm_bytes: &[u8],
+    options: &RunnerOptions,
+) -> Result<TestResult, Error> {
+    if options.fuel.is_none() {
+        return Ok(run_instance(wasm_bytes, options)?.0);
+    }
+    let module =
+        deserialize_buffer::<Module>(wasm_bytes).map_err(|e| Error::Parse(e.to_string()))?;
+    run_module(module, options)
+}
+
+/// Runs the tests in an already parsed module, metering its fuel if the options limit it.
+pub(crate) fn run_module(mut module: Module, options: &RunnerOptions) -> Result<TestResult, Error> {
+    if let Some(fuel) = options.fuel {
+        fuel::meter(&mut module, fuel);
+    }
+    let serialized = module
+        .to_bytes()
+        .map_err(|e| Error::Serialization(e.to_string()))?;
+    Ok(run_metered_instance(&serialized, options)?.0)
+}
+
+/// Runs the tests in a module instrumented by [`fuel::meter`], reporting a timeout if it ran out
+/// of fuel.
+fn run_metered_instance(
+    wasm_bytes: &[u8],
+    options: &RunnerOptions,
+) -> Result<(TestResult, Option<ModuleRef>), Error> {
+    let (result, instance) = run_instance(wasm_bytes, options)?;
+    let out_of_fuel = instance
+        .as_ref()
+        .and_then(fuel::remaining_fuel)
+        .is_some_and(|fuel| fuel < 0);
+    if result == TestResult::Failed && out_of_fuel {
+        Ok((TestResult::Timeout, instance))
+    } else {
+        Ok((result, instance))
+    }
+}
+
+/// Runs the tests, returning the instance on which they ran unless its start function trapped.
+fn run_instance(
+    wasm_bytes: &[u8],
+    options: &RunnerOptions,
+) -> Result<(TestResult, Option<ModuleRef>), Error> {
+    let module = wasmi::Module::from_buffer(wasm_bytes).map_err(|e| Error::Parse(e.to_string()))?;
+    let instance = ModuleInstance::new(&module, &ImportsBuilder::default())
+        .map_err(|e| Error::Instantiation(e.to_string()))?;
+    let instance = match instance.run_start(&mut NopExternals) {
+        Ok(instance) => instance,
+        Err(trap) => return Ok((TestResult::from_trap(trap.kind()), None)),
+    };
+    let entry_point = match instance.export_by_name(&options.entry_point) {
+        Some(ExternVal::Func(function)) => function,
+        _ => return Err(Error::MissingEntryExport(options.entry_point.clone())),
+    };
+    if entry_point.signature().params() != [ValueType::I32, ValueType::I32] {
+        return Err(Error::EntryPointSignature(options.entry_point.clone()));
+    }
     let result = instance.invoke_export(
-        "main",
+        &options.entry_point,
         &[RuntimeValue::from(0), RuntimeValue::from(0)],
         &mut NopExternals,
     );
-    match result {
+    let result = match result {
         Ok(_) => TestResult::Passed,
-        Err(_) => TestResult::Failed,
-    }
+        Err(wasmi::Error::Trap(trap)) => TestResult::from_trap(trap.kind()),
+        Err(_) => return Err(Error::EntryPointSignature(options.entry_point.clone())),
+    };
+    Ok((result, Some(instance)))
 }
 
-#[derive(Debug, Eq, PartialEq)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
 pub enum TestResult {
     Passed,
+    /// A test failed, i.e. the module panicked and thus reached an `unreachable` instruction.
     Failed,
+    /// Execution stopped on a trap other than that raised by a panic.
+    Trapped(TrapKind),
+    /// The tests used up their fuel.
+    Timeout,
+}
+
+/// Kind of trap, other than `unreachable`, on which execution of a module stopped.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum TrapKind {
+    MemoryAccessOutOfBounds,
+    TableAccessOutOfBounds,
+    ElemUninitialized,
+    DivisionByZero,
+    InvalidConversionToInt,
+    StackOverflow,
+    UnexpectedSignature,
+    Host,
+}
+
+impl TestResult {
+    fn from_trap(kind: &wasmi::TrapKind) -> TestResult {
+        let kind = match kind {
+            wasmi::TrapKind::Unreachable => return TestResult::Failed,
+            wasmi::TrapKind::MemoryAccessOutOfBounds => TrapKind::MemoryAccessOutOfBounds,
+            wasmi::TrapKind::TableAccessOutOfBounds => TrapKind::TableAccessOutOfBounds,
+            wasmi::TrapKind::ElemUninitialized => TrapKind::ElemUninitialized,
+            wasmi::TrapKind::DivisionByZero => TrapKind::DivisionByZero,
+            wasmi::TrapKind::InvalidConversionToInt => TrapKind::InvalidConversionToInt,
+            wasmi::TrapKind::StackOverflow => TrapKind::StackOverflow,
+            wasmi::TrapKind::UnexpectedSignature => TrapKind::UnexpectedSignature,
+            wasmi::TrapKind::Host(_) => TrapKind::Host,
+        };
+        TestResult::Trapped(kind)
+    }
 }
 
 #[cfg(test)]
-mod tests {
+pub(crate) mod tests {
     use crate::runner;
-    use crate::runner::TestResult;
+    use crate::runner::{RunnerOptions, TestResult, TrapKind};
+    use crate::Error;
+    use parity_wasm::builder::ModuleBuilder;
+    use parity_wasm::elements::{Instruction, Instructions, ValueType};
 
     #[test]
     fn runs_tests_which_pass() {
-        let result = runner::run_tests(include_bytes!("./res/cases/test-example.wasm"));
+        let result = runner::run_tests(include_bytes!("./res/cases/test-example.wasm")).unwrap();
 
         assert_eq!(result, TestResult::Passed);
     }
 
     #[test]
     fn runs_tests_which_fail() {
-        let result = runner::run_tests(include_bytes!("./res/cases/test-example-failing.wasm"));
+        let result =
+            runner::run_tests(include_bytes!("./res/cases/test-example-failing.wasm")).unwrap();
 
         assert_eq!(result, TestResult::Failed);
     }
+
+    #[test]
+    fn reports_trap_other_than_panic() {
+        let module = test_module(vec![
+            Instruction::I32Const(1),
+            Instruction::I32Const(0),
+            Instruction::I32DivU,
+            Instruction::Drop,
+            Instruction::End,
+        ]);
+
+        let result = runner::run_tests(&module).unwrap();
+
+        assert_eq!(result, TestResult::Trapped(TrapKind::DivisionByZero));
+    }
+
+    #[test]
+    fn fails_on_invalid_module() {
+        let module = test_module(vec![Instruction::Drop, Instruction::End]);
+
+        let result = runner::run_tests(&module);
+
+        assert!(matches!(result, Err(Error::Parse(_))));
+    }
+
+    #[test]
+    fn fails_on_module_with_unresolved_imports() {
+        let module = ModuleBuilder::new()
+            .import()
+            .module("env")
+            .field("missing")
+            .external()
+            .func(0)
+            .build()
+            .function()
+            .signature()
+            .build()
+            .body()
+            .build()
+            .build()
+            .build()
+            .to_bytes()
+            .unwrap();
+
+        let result = runner::run_tests(&module);
+
+        assert!(matches!(result, Err(Error::Instantiation(_))));
+    }
+
+    #[test]
+    fn fails_on_missing_entry_point() {
+        let module = test_module(vec![Instruction::End]);
+
+        let result = runner::run_tests_with_options(
+            &module,
+            &RunnerOptions {
+                entry_point: String::from("test"),
+                ..RunnerOptions::default()
+            },
+        );
+
+        assert!(matches!(result, Err(Error::MissingEntryExport(name)) if name == "test"));
+    }
+
+    #[test]
+    fn fails_on_entry_point_with_other_signature() {
+        let module = ModuleBuilder::new()
+            .function()
+            .signature()
+            .build()
+            .body()
+            .build()
+            .build()
+            .export()
+            .field("_start")
+            .internal()
+            .func(0)
+            .build()
+            .build()
+            .to_bytes()
+            .unwrap();
+
+        let result = runner::run_tests_with_options(
+            &module,
+            &RunnerOptions {
+                entry_point: String::from("_start"),
+                ..RunnerOptions::default()
+            },
+        );
+
+        assert!(matches!(result, Err(Error::EntryPointSignature(name)) if name == "_start"));
+    }
+
+    pub(crate) fn test_module(instructions: Vec<Instruction>) -> Vec<u8> {
+        ModuleBuilder::new()
+            .function()
+            .signature()
+            .with_params(vec![ValueType::I32, ValueType::I32])
+            .build()
+            .body()
+            .with_instructions(Instructions::new(instructions))
+            .build()
+            .build()
+            .export()
+            .field("main")
+            .internal()
+            .func(0)
+            .build()
+            .build()
+            .to_bytes()
+            .unwrap()
+    }
 }
@@ -0,0 +1,198 @@
+//! Annotations with which users exclude code from mutation, e.g. logging or `Debug` impls whose
+//! mutants no test is expected to kill.
+//!
+//! A line ending in a `// mutants: skip` comment is not mutated. A function is not mutated if its
+//! declaration carries such a comment or a `#[mutants::skip]` attribute, either on the line of
+//! the `fn` or on the attribute and comment lines directly above it. The annotations are read
+//! from the local source files named by the DWARF debugging information, so they are only honoured
+//! for modules built with debug information on the machine running the tests.
+//!
+//! A procedural macro may instead record the paths of the functions it annotates in custom
+//! sections named `mutants.skip`, e.g. with a `#[link_section = "mutants.skip"]` static, which
+//! survive the linker and do not depend on the source files.
+
+use crate::candidates::CandidateFunction;
+use crate::debug_info::{subprograms, SourceFiles};
+use crate::SourceLocation;
+use parity_wasm::elements::Module;
+use std::collections::HashMap;
+
+/// Name of the custom sections listing the functions not to mutate.
+pub(crate) const SKIP_SECTION: &str = "mutants.skip";
+
+const SKIP_COMMENT: &str = "mutants:skip";
+const SKIP_ATTRIBUTE: &str = "mutants::skip";
+
+/// Returns the patterns listed in the `mutants.skip` custom sections, separated by NUL characters
+/// or newlines. The linker concatenates the sections of all object files into one.
+pub(crate) fn section_patterns(module: &Module) -> Vec<String> {
+    module
+        .custom_sections()
+        .filter(|section| section.name() == SKIP_SECTION)
+        .flat_map(|section| {
+            String::from_utf8_lossy(section.payload())
+                .split(['\0', '\n'])
+                .map(str::trim)
+                .filter(|pattern| !pattern.is_empty())
+                .map(String::from)
+                .collect::<Vec<_>>()
+        })
+        .collect()
+}
+
+/// Skip annotations in the source files of a module, which are read once each when first needed.
+pub(crate) struct SkipAnnotations {
+    /// Declarations of the functions of the module, by body index.
+    declarations: HashMap<usize, SourceLocation>,
+    sources: SourceFiles,
+}
+
+impl SkipAnnotations {
+    /// Prepares to read the annotations of the module, which must have been parsed from the given
+    /// contents.
+    pub(crate) fn new(module: &Module, module_contents: &[u8]) -> Self {
+        SkipAnnotations {
+            declarations: subprograms(module, module_contents)
+                .into_iter()
+                .filter_map(|subprogram| Some((subprogram.body_index, subprogram.declaration?)))
+                .collect(),
+            sources: SourceFiles::default(),
+        }
+    }
+
+    /// Removes the candidates whose declaration is annotated, along with the closures defined in
+    /// them.
+    pub(crate) fn remove_skipped_functions(&mut self, candidates: &mut Vec<CandidateFunction>) {
+        let mut skipped = Vec::new();
+        for candidate in candidates.iter() {
+            let declaration = match self.declarations.get(&candidate.body_index) {
+                Some(declaration) => declaration.clone(),
+                None => continue,
+            };
+            if self.skips_function(&declaration) {
+                skipped.push(candidate.path.enclosing_function());
+            }
+        }
+        candidates.retain(|candidate| !skipped.contains(&candidate.path.enclosing_function()));
+    }
+
+    /// Returns whether the given line carries a `// mutants: skip` comment.
+    pub(crate) fn skips_line(&mut self, location: &SourceLocation) -> bool {
+        self.sources.line(location).is_some_and(has_skip_comment)
+    }
+
+    /// Returns whether the function declared at the given location is annotated.
+    pub(crate) fn skips_function(&mut self, declaration: &SourceLocation) -> bool {
+        let lines = match self.sources.lines(&declaration.file) {
+            Some(lines) => lines,
+            None => return false,
+        };
+        is_function_skipped(lines, declaration.line as usize)
+    }
+}
+
+/// Returns whether the function declared on the given line, starting at 1, is annotated on that
+/// line or on the attribute and comment lines directly above it.
+fn is_function_skipped(lines: &[String], declaration_line: usize) -> bool {
+    let declaration = match declaration_line.checked_sub(1).and_then(|i| lines.get(i)) {
+        Some(line) => line,
+        None => return false,
+    };
+    if has_skip_comment(declaration) || has_skip_attribute(declaration) {
+        return true;
+    }
+    lines[..declaration_line - 1]
+        .iter()
+        .rev()
+        .map(|line| line.trim())
+        .take_while(|line| line.starts_with("#[") || line.starts_with("//"))
+        .any(|line| has_skip_comment(line) || has_skip_attribute(line))
+}
+
+/// Whether the line has a skip comment. Every `//` is tried as the start of the comment, since
+/// the code before the comment may contain `//` as well, e.g. in a URL.
+fn has_skip_comment(line: &str) -> bool {
+    line.match_indices("//").any(|(start, _)| {
+        let comment: String = line[start + 2..].split_whitespace().collect();
+        comment.starts_with(SKIP_COMMENT)
+    })
+}
+
+fn has_skip_attribute(line: &str) -> bool {
+    line.find("#[")
+        .is_some_and(|start| line[start..].contains(SKIP_ATTRIBUTE))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::skip::{has_skip_comment, is_function_skipped, section_patterns, SKIP_SECTION};
+    use parity_wasm::builder::ModuleBuilder;
+    use parity_wasm::elements::{CustomSection, Section};
+
+    fn lines(source: &str) -> Vec<String> {
+        source.lines().map(String::from).collect()
+    }
+
+    #[test]
+    fn recognises_skip_comments() {
+        assert!(has_skip_comment("    log(x); // mutants: skip"));
+        assert!(has_skip_comment(
+            "    log(x); //mutants:skip because it only logs"
+        ));
+        assert!(has_skip_comment(
+            "    let url = \"http://x\"; // mutants: skip"
+        ));
+        assert!(!has_skip_comment("    log(x); // mutants are welcome"));
+        assert!(!has_skip_comment("    log(\"mutants: skip\");"));
+    }
+
+    #[test]
+    fn skips_function_with_attribute_above_declaration() {
+        let source = lines("/// Logs.\n#[mutants::skip]\n#[inline]\npub fn log() {\n}\n");
+
+        assert!(is_function_skipped(&source, 4));
+    }
+
+    #[test]
+    fn skips_function_with_conditional_attribute() {
+        let source = lines("#[cfg_attr(test, mutants::skip)]\nfn log() {}\n");
+
+        assert!(is_function_skipped(&source, 2));
+    }
+
+    #[test]
+    fn skips_function_with_comment_on_declaration() {
+        let source = lines("fn log() { // mutants: skip\n}\n");
+
+        assert!(is_function_skipped(&source, 1));
+    }
+
+    #[test]
+    fn does_not_skip_function_after_annotated_one() {
+        let source = lines("#[mutants::skip]\nfn log() {}\n\nfn compute() {}\n");
+
+        assert!(!is_function_skipped(&source, 4));
+    }
+
+    #[test]
+    fn does_not_skip_function_declared_beyond_end_of_file() {
+        assert!(!is_function_skipped(&lines("fn log() {}\n"), 2));
+        assert!(!is_function_skipped(&lines("fn log() {}\n"), 0));
+    }
+
+    #[test]
+    fn reads_patterns_from_skip_sections() {
+        let mut module = ModuleBuilder::new().build();
+        module
+            .sections_mut()
+            .push(Section::Custom(CustomSection::new(
+                String::from(SKIP_SECTION),
+                b"app::log\0app::Point::fmt\0\napp::debug\0".to_vec(),
+            )));
+
+        assert_eq!(
+            section_patterns(&module),
+            vec!["app::log", "app::Point::fmt", "app::debug"]
+        );
+    }
+}
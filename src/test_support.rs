@@ -0,0 +1,153 @@
+use parity_wasm::builder::{self, ModuleBuilder};
+use parity_wasm::elements::{
+    BlockType, FunctionNameSubsection, Instruction, Instructions, NameSection, Section, ValueType,
+};
+
+/// Builds a module in which `main`, the entry point of the tests, calls each of the given
+/// functions in turn. The functions take no parameters, return nothing, and are listed in the
+/// name section under the given names.
+pub(crate) fn module_with_functions(functions: &[(&str, Vec<Instruction>)]) -> Vec<u8> {
+    module_with_imported_functions(&[], functions)
+}
+
+/// Like [`module_with_functions`], but the module first imports functions with the given names
+/// from the `env` module, so that the index of each defined function in the function index space
+/// differs from the index of its body in the code section.
+pub(crate) fn module_with_imported_functions(
+    imports: &[&str],
+    functions: &[(&str, Vec<Instruction>)],
+) -> Vec<u8> {
+    let first_defined = imports.len() as u32;
+    let mut main_body: Vec<Instruction> = (first_defined + 1
+        ..=first_defined + functions.len() as u32)
+        .map(Instruction::Call)
+        .collect();
+    main_body.push(Instruction::End);
+    let mut builder = ModuleBuilder::new();
+    if !imports.is_empty() {
+        let signature = builder.push_signature(builder::signature().build_sig());
+        for import in imports {
+            builder = builder
+                .import()
+                .module("env")
+                .field(import)
+                .external()
+                .func(signature)
+                .build();
+        }
+    }
+    builder = builder
+        .function()
+        .signature()
+        .with_params(vec![ValueType::I32, ValueType::I32])
+        .build()
+        .body()
+        .with_instructions(Instructions::new(main_body))
+        .build()
+        .build();
+    for (_, instructions) in functions {
+        builder = builder
+            .function()
+            .signature()
+            .build()
+            .body()
+            .with_instructions(Instructions::new(instructions.clone()))
+            .build()
+            .build();
+    }
+    let mut module = builder
+        .export()
+        .field("main")
+        .internal()
+        .func(first_defined)
+        .build()
+        .build();
+
+    let mut names = FunctionNameSubsection::default();
+    for (index, name) in imports.iter().enumerate() {
+        names.names_mut().insert(index as u32, String::from(*name));
+    }
+    names
+        .names_mut()
+        .insert(first_defined, String::from("main"));
+    for (index, (name, _)) in functions.iter().enumerate() {
+        names
+            .names_mut()
+            .insert(first_defined + index as u32 + 1, String::from(*name));
+    }
+    module
+        .sections_mut()
+        .push(Section::Name(NameSection::new(None, Some(names), None)));
+    module.to_bytes().unwrap()
+}
+
+/// A function which panics if `0 < 0`, so that every mutant of the comparison or the branch
+/// is killed.
+pub(crate) fn killable_function() -> (&'static str, Vec<Instruction>) {
+    (
+        "killed::check",
+        vec![
+            Instruction::Block(BlockType::NoResult),
+            Instruction::Block(BlockType::NoResult),
+            Instruction::I32Const(0),
+            Instruction::I32Const(0),
+            Instruction::I32LtU,
+            Instruction::BrIf(0),
+            Instruction::Br(1),
+            Instruction::End,
+            Instruction::Unreachable,
+            Instruction::End,
+            Instruction::End,
+        ],
+    )
+}
+
+/// A function which computes `2 > 1` and discards the result, so that mutating the comparison
+/// goes unnoticed.
+pub(crate) fn surviving_function() -> (&'static str, Vec<Instruction>) {
+    (
+        "survivor::compute",
+        vec![
+            Instruction::I32Const(2),
+            Instruction::I32Const(1),
+            Instruction::I32GtU,
+            Instruction::Drop,
+            Instruction::End,
+        ],
+    )
+}
+
+/// Removes the custom sections with the given name from an encoded module, leaving every other
+/// byte, and so the offsets within the code section, untouched.
+pub(crate) fn without_custom_section(contents: &[u8], name: &str) -> Vec<u8> {
+    let mut result = contents[..8].to_vec();
+    let mut position = 8;
+    while position < contents.len() {
+        let start = position;
+        let id = contents[position];
+        position += 1;
+        let (size, length) = read_leb_u32(&contents[position..]);
+        position += length;
+        let payload = &contents[position..position + size as usize];
+        position += size as usize;
+        if id == 0 {
+            let (name_length, length) = read_leb_u32(payload);
+            if &payload[length..length + name_length as usize] == name.as_bytes() {
+                continue;
+            }
+        }
+        result.extend_from_slice(&contents[start..position]);
+    }
+    result
+}
+
+fn read_leb_u32(bytes: &[u8]) -> (u32, usize) {
+    let mut result = 0;
+    for (index, byte) in bytes.iter().enumerate() {
+        result |= u32::from(byte & 0x7f) << (7 * index);
+        if byte & 0x80 == 0 {
+            return (result, index + 1);
+        }
+    }
+    panic!("unterminated LEB128 integer")
+}
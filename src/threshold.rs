@@ -0,0 +1,215 @@
+//! Minimum mutation scores, for using mutation testing as a quality gate.
+//!
+//! A threshold either applies to the mutants of the functions matched by a pattern (see
+//! [`Pattern`]), e.g. `parser=90`, or, without a pattern, to all other mutants, e.g. `70`. With
+//! both, the mutants of `parser` must score at least 90% and the rest at least 70%.
+
+use crate::candidates::{Pattern, SymbolPath};
+use crate::{Error, MutationTestResults, Totals};
+use std::fmt::{self, Display, Formatter};
+use std::str::FromStr;
+
+/// Minimum mutation score of some of the mutants of a run.
+#[derive(Clone, Debug)]
+pub struct Threshold {
+    pattern: Option<Pattern>,
+    minimum: f64,
+}
+
+impl Threshold {
+    /// Creates a threshold for the mutants of the functions matched by the given pattern, or for
+    /// the mutants matched by no other threshold if there is none. The minimum is a percentage.
+    pub fn new(pattern: Option<&str>, minimum: f64) -> Result<Self, Error> {
+        if !(0.0..=100.0).contains(&minimum) {
+            return Err(Error::InvalidThreshold(format!(
+                "{} is not a percentage",
+                minimum
+            )));
+        }
+        Ok(Threshold {
+            pattern: pattern
+                .map(|pattern| Pattern::new(pattern.trim_end_matches("::")))
+                .transpose()?,
+            minimum,
+        })
+    }
+
+    /// Parses a threshold written as `PATTERN=PERCENT` or as `PERCENT` alone.
+    pub fn parse(text: &str) -> Result<Self, Error> {
+        let (pattern, minimum) = match text.rsplit_once('=') {
+            Some((pattern, minimum)) => (Some(pattern), minimum),
+            None => (None, text),
+        };
+        let minimum =
+            minimum.trim().trim_end_matches('%').parse().map_err(|_| {
+                Error::InvalidThreshold(format!("`{}` is not a percentage", minimum))
+            })?;
+        Threshold::new(pattern, minimum)
+    }
+
+    pub fn pattern(&self) -> Option<&Pattern> {
+        self.pattern.as_ref()
+    }
+
+    pub fn minimum(&self) -> f64 {
+        self.minimum
+    }
+}
+
+impl FromStr for Threshold {
+    type Err = Error;
+
+    fn from_str(text: &str) -> Result<Self, Error> {
+        Threshold::parse(text)
+    }
+}
+
+impl Display for Threshold {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match &self.pattern {
+            Some(pattern) => write!(f, "{}={}", pattern, self.minimum),
+            None => write!(f, "{}", self.minimum),
+        }
+    }
+}
+
+/// A threshold which the mutants it applies to did not reach.
+#[derive(Clone, Debug)]
+pub struct FailedThreshold {
+    pub threshold: Threshold,
+    /// Mutation score of the mutants to which the threshold applies.
+    pub score: f64,
+    /// Whether the threshold has no pattern but others have, so that it applies to the mutants
+    /// of the remaining functions.
+    remaining: bool,
+}
+
+impl Display for FailedThreshold {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str("the mutation score")?;
+        if let Some(pattern) = &self.threshold.pattern {
+            write!(f, " of {}", pattern)?;
+        } else if self.remaining {
+            f.write_str(" of the remaining functions")?;
+        }
+        write!(
+            f,
+            " is {:.1}%, below the threshold of {}%",
+            self.score, self.threshold.minimum
+        )
+    }
+}
+
+/// Returns the thresholds which the results do not reach. Thresholds whose mutants are all
+/// unviable or accepted, or which apply to no mutant, are reached.
+pub fn failed_thresholds(
+    results: &MutationTestResults,
+    thresholds: &[Threshold],
+) -> Vec<FailedThreshold> {
+    let mut totals = vec![Totals::default(); thresholds.len()];
+    let any_pattern = thresholds
+        .iter()
+        .any(|threshold| threshold.pattern.is_some());
+    for result in &results.mutants {
+        let path = SymbolPath::parse(result.mutation.function_name());
+        let matches = |threshold: &Threshold| {
+            threshold
+                .pattern
+                .as_ref()
+                .is_some_and(|pattern| pattern.matches(&path))
+        };
+        let matched = thresholds.iter().any(matches);
+        for (threshold, totals) in thresholds.iter().zip(&mut totals) {
+            if matches(threshold) || (!matched && threshold.pattern.is_none()) {
+                totals.add(result.outcome);
+            }
+        }
+    }
+    thresholds
+        .iter()
+        .zip(totals)
+        .filter_map(|(threshold, totals)| {
+            let score = totals.mutation_score()?;
+            (score < threshold.minimum).then(|| FailedThreshold {
+                threshold: threshold.clone(),
+                score,
+                remaining: any_pattern && threshold.pattern.is_none(),
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::test_support::{killable_function, module_with_functions, surviving_function};
+    use crate::threshold::{failed_thresholds, Threshold};
+    use crate::{Error, MutationTestResults, MutationTestRun};
+
+    /// Runs the mutants of a function whose mutants are all killed and of one whose mutants all
+    /// survive.
+    fn results() -> MutationTestResults {
+        let module_contents = module_with_functions(&[killable_function(), surviving_function()]);
+        MutationTestRun::new(&module_contents)
+            .include_module("killed")
+            .include_module("survivor")
+            .run()
+            .unwrap()
+    }
+
+    fn thresholds(texts: &[&str]) -> Vec<Threshold> {
+        texts
+            .iter()
+            .map(|text| Threshold::parse(text).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn applies_threshold_without_pattern_to_all_mutants() {
+        let results = results();
+
+        let failed = failed_thresholds(&results, &thresholds(&["90"]));
+
+        assert_eq!(failed.len(), 1);
+        assert_eq!(Some(failed[0].score), results.mutation_score());
+    }
+
+    #[test]
+    fn applies_threshold_without_pattern_to_mutants_matched_by_no_other() {
+        let results = results();
+
+        let failed = failed_thresholds(&results, &thresholds(&["killed::=90", "50"]));
+
+        assert_eq!(failed.len(), 1);
+        assert_eq!(
+            failed[0].to_string(),
+            "the mutation score of the remaining functions is 0.0%, below the threshold of 50%"
+        );
+        assert!(failed_thresholds(&results, &thresholds(&["killed=100", "survivor=0"])).is_empty());
+    }
+
+    #[test]
+    fn describes_failed_threshold() {
+        let failed = failed_thresholds(&results(), &thresholds(&["survivor=75%"]));
+
+        assert_eq!(
+            failed[0].to_string(),
+            "the mutation score of survivor is 0.0%, below the threshold of 75%"
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_thresholds() {
+        assert!(matches!(
+            Threshold::parse("parser=high"),
+            Err(Error::InvalidThreshold(_))
+        ));
+        assert!(matches!(
+            Threshold::parse("120"),
+            Err(Error::InvalidThreshold(_))
+        ));
+        assert!(matches!(
+            Threshold::parse("re:(=90"),
+            Err(Error::InvalidPattern(_, _))
+        ));
+    }
+}